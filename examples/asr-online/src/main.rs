@@ -1,7 +1,8 @@
 use sherpa_rs_next::{
     asr::online::{
-        AsrOnlineConfig, AsrOnlineRecognizer, paraformer::ParaformerAsrOnlineConfig,
-        zipformer::ZipformerAsrOnlineConfig, zipformer_ctc::ZipformerCtcAsrOnlineConfig,
+        AsrOnlineConfig, AsrOnlineRecognizer, nemo_ctc::NemoCtcAsrOnlineConfig,
+        paraformer::ParaformerAsrOnlineConfig, zipformer::ZipformerAsrOnlineConfig,
+        zipformer_ctc::ZipformerCtcAsrOnlineConfig,
     },
     audio::read_audio_file,
 };
@@ -16,6 +17,7 @@ fn main() -> anyhow::Result<()> {
         "paraformer" => paraformer(),
         "zipformer_ctc" => zipformer_ctc(),
         "zipformer" => zipformer(),
+        "nemo_ctc" => nemo_ctc(),
         _ => anyhow::bail!("Invalid model type"),
     };
 
@@ -23,7 +25,7 @@ fn main() -> anyhow::Result<()> {
     let mut recognizer = AsrOnlineRecognizer::create_with_config(config)?;
 
     for chunk in samples.chunks(1600) {
-        let result = recognizer.transcribe(sample_rate, &chunk)?;
+        let result = recognizer.transcribe(sample_rate, chunk)?;
         if result.text().is_empty() {
             continue;
         }
@@ -34,6 +36,12 @@ fn main() -> anyhow::Result<()> {
         }
     }
 
+    // flush the tail of the file
+    let result = recognizer.finish()?;
+    if !result.text().is_empty() {
+        println!("🎉 Final: {}", result.text());
+    }
+
     Ok(())
 }
 
@@ -108,3 +116,23 @@ fn zipformer() -> AsrOnlineConfig {
 
     Box::new(config)
 }
+
+/// https://github.com/k2-fsa/sherpa-onnx/releases/download/asr-models/sherpa-onnx-nemo-streaming-fast-conformer-ctc-en-80ms.tar.bz2
+fn nemo_ctc() -> AsrOnlineConfig {
+    let model = std::env::args()
+        .nth(3)
+        .expect("Missing model path argument");
+    let tokens = std::env::args()
+        .nth(4)
+        .expect("Missing tokens path argument");
+    let mut config = NemoCtcAsrOnlineConfig::default();
+    config
+        .with_model(&model)
+        .with_model_tokens(&tokens)
+        .with_enable_endpoint(true)
+        .with_rule1_min_trailing_silence(2.4)
+        .with_rule2_min_trailing_silence(1.2)
+        .with_rule3_min_utterance_length(300.0);
+
+    Box::new(config)
+}
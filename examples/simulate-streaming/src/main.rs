@@ -1,9 +1,12 @@
-use std::{sync::mpsc, time::Instant};
+use std::sync::mpsc;
 
 use colored::Colorize as _;
 use cpal::traits::{DeviceTrait as _, HostTrait as _, StreamTrait as _};
 use sherpa_rs_next::{
-    asr::offline::{AsrOfflineRecognizer, sense_voice::SenseVoiceAsrOfflineConfig},
+    asr::{
+        offline::{AsrOfflineRecognizer, sense_voice::SenseVoiceAsrOfflineConfig},
+        simulated_streaming::{SimulatedStreamingRecognizer, SimulatedStreamingResult},
+    },
     vad::{Vad, silero::SileroVadConfig},
 };
 
@@ -43,7 +46,7 @@ fn main() -> anyhow::Result<()> {
         .with_threshold(0.5)
         .with_num_threads(2);
 
-    let mut vad = Vad::create(vad_config, 20.0)?;
+    let vad = Vad::create(vad_config, 20.0)?;
 
     let mut recognizer_config = SenseVoiceAsrOfflineConfig::default();
     recognizer_config
@@ -62,14 +65,11 @@ fn main() -> anyhow::Result<()> {
 
     let stream = input_device_sender(audio_tx, device_id)?;
 
-    // Audio processing loop
-    let window_size = 512;
-    let mut buffer = Vec::new();
-    let mut offset = 0;
-    let mut speech_started = false;
-    let mut speech_start_index = 0; // Index in buffer where speech started
-    let mut started_time = Instant::now();
-    let mut last_result = String::new();
+    let mut recognizer = SimulatedStreamingRecognizer::new(vad, recognizer);
+    recognizer
+        .with_sample_rate(target_sample_rate as u32)
+        .with_window_size(512)
+        .with_partial_interval(0.2);
 
     loop {
         // Check for stop signal
@@ -78,11 +78,11 @@ fn main() -> anyhow::Result<()> {
         }
 
         // Receive audio samples
-        match audio_rx.try_recv() {
+        let results = match audio_rx.try_recv() {
             Ok(samples) => {
                 let samples =
                     resample_audio(samples.samples, samples.sample_rate, target_sample_rate);
-                buffer.extend_from_slice(&samples);
+                recognizer.accept_waveform(&samples)?
             }
             Err(mpsc::TryRecvError::Empty) => {
                 // No data available, continue
@@ -93,84 +93,30 @@ fn main() -> anyhow::Result<()> {
                 eprintln!("Audio channel disconnected");
                 break;
             }
-        }
-
-        // Process audio in windows
-        while offset + window_size <= buffer.len() {
-            let window = &buffer[offset..offset + window_size];
-            vad.accept_waveform(window.to_vec());
-
-            if !speech_started && vad.is_speech() {
-                speech_started = true;
-                // Record the buffer index where speech started
-                // We need to account for the offset that has been processed
-                speech_start_index = offset;
-                started_time = Instant::now();
-            }
-
-            offset += window_size;
-        }
+        };
 
-        // Keep buffer size manageable when not in speech
-        if !speech_started {
-            if buffer.len() > 10 * window_size {
-                let keep_size = 10 * window_size;
-                buffer = buffer[buffer.len() - keep_size..].to_vec();
-                offset = 0;
-            }
-        }
+        print_results(&results);
+    }
 
-        // Perform recognition if speech detected and enough time elapsed
-        let elapsed = started_time.elapsed();
-        if speech_started && elapsed.as_secs_f32() > 0.2 && !buffer.is_empty() {
-            // Use only the audio from speech start to current
-            let speech_audio = if speech_start_index < buffer.len() {
-                &buffer[speech_start_index..]
-            } else {
-                &buffer
-            };
-            let result = recognizer.transcribe(target_sample_rate as u32, speech_audio)?;
-            if !result.text().is_empty() && result.text() != last_result {
-                // Display intermediate result in yellow
-                print!("\r{}", colored::ColoredString::from(result.text()).yellow());
-                std::io::Write::flush(&mut std::io::stdout()).unwrap();
-                last_result = result.text().to_string();
-            }
-            started_time = Instant::now();
-        }
+    print_results(&recognizer.flush()?);
 
-        // Process completed speech segments from VAD
-        while !vad.is_empty() {
-            let segment = vad.front();
+    println!("\nStopped.");
 
-            // Use only the audio from speech start to current for final recognition
-            // This ensures we don't include pre-speech audio that causes duplication
-            let result = if !buffer.is_empty() && speech_start_index < buffer.len() {
-                let speech_audio = &buffer[speech_start_index..];
-                recognizer.transcribe(target_sample_rate as u32, speech_audio)
-            } else if !buffer.is_empty() {
-                recognizer.transcribe(target_sample_rate as u32, &buffer)
-            } else {
-                recognizer.transcribe(target_sample_rate as u32, &segment.samples)
-            }?;
+    drop(stream);
+    Ok(())
+}
 
-            // Display final result in default color with emotion if available
+fn print_results(results: &[SimulatedStreamingResult]) {
+    for result in results {
+        if result.is_final() {
+            // Display final result in default color
             println!("\n✅ Final: {}", result.text());
-
-            vad.pop();
-            // Clear buffer after processing segment to avoid overlap with next segment
-            buffer.clear();
-            offset = 0;
-            speech_started = false;
-            speech_start_index = 0;
-            last_result.clear();
+        } else {
+            // Display intermediate result in yellow
+            print!("\r{}", colored::ColoredString::from(result.text()).yellow());
+            std::io::Write::flush(&mut std::io::stdout()).unwrap();
         }
     }
-
-    println!("\nStopped.");
-
-    drop(stream);
-    Ok(())
 }
 
 fn input_device_sender(
@@ -2,7 +2,10 @@ use std::path::Path;
 
 use sherpa_rs_next::{
     audio::write_audio_file,
-    tts::offline::{OfflineTTS, kokoro::KokoroTTSConfig},
+    tts::offline::{
+        GenerationConfig, OfflineTTS,
+        kokoro::{KokoroTTSConfig, KokoroVoices},
+    },
 };
 
 fn main() -> anyhow::Result<()> {
@@ -29,7 +32,22 @@ fn main() -> anyhow::Result<()> {
 
     let tts = OfflineTTS::create(&config)?;
 
-    let audio = tts.generate("Hello, world!, 你好，世界！", 0, 1.0)?;
+    println!("Speakers: {}", tts.num_speakers());
+    let sid = KokoroVoices::for_model(&tts)?.sid("zf_xiaobei")?;
+    let mut generation = GenerationConfig::default();
+    generation.with_sid(sid);
+    let audio = tts.generate_with_callback(
+        "Hello, world!, 你好，世界！",
+        &generation,
+        |samples, progress| {
+            println!(
+                "Chunk of {} samples, progress {:.0}%",
+                samples.len(),
+                progress * 100.0
+            );
+            true
+        },
+    )?;
     println!("Audio duration: {} seconds", audio.duration());
     println!("Audio sample rate: {} Hz", audio.sample_rate());
     println!("Audio samples: {}", audio.samples().len());
@@ -2,8 +2,12 @@ use serde::{Deserialize, Serialize};
 
 pub mod offline;
 pub mod online;
+pub mod simulated_streaming;
+pub mod stabilizer;
+pub mod two_pass;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct RecognizerJsonResult {
     pub text: String,
     pub tokens: Vec<String>,
@@ -12,15 +16,96 @@ pub struct RecognizerJsonResult {
     pub lm_probs: Vec<f32>,
     pub context_scores: Vec<f32>,
     pub segment: i32,
-    pub words: Vec<Word>,
+    // word ids of a decoding graph, e.g. `ctc_fst_decoder_graph`
+    pub words: Vec<i32>,
     pub start_time: f32,
     pub is_final: bool,
     pub is_eof: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct Word {
     pub word: String,
     pub start: f32,
     pub end: f32,
 }
+
+/// Groups tokens into words, a word starts at a token with a leading space
+/// or `▁` and every CJK character is a word of its own.
+/// A word ends where the next one starts, the last word at its last token
+pub(crate) fn words_from_tokens(tokens: &[String], timestamps: &[f32]) -> Vec<Word> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut new_word = true;
+    for (token, &start) in tokens.iter().zip(timestamps) {
+        let text = token.trim_start_matches([' ', '▁']);
+        if text.len() != token.len() {
+            new_word = true;
+        }
+        if text.is_empty() {
+            continue;
+        }
+        if let Some(last) = words.last_mut() {
+            last.end = start;
+        }
+        match words.last_mut() {
+            Some(last) if !new_word && !text.starts_with(is_cjk) => last.word.push_str(text),
+            _ => words.push(Word {
+                word: text.to_string(),
+                start,
+                end: start,
+            }),
+        }
+        new_word = text.ends_with(is_cjk);
+    }
+    words
+}
+
+fn is_cjk(c: char) -> bool {
+    // kana, CJK ideographs and hangul
+    matches!(c, '\u{3040}'..='\u{30ff}' | '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}' | '\u{ac00}'..='\u{d7af}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recognizer_json_result_partial() {
+        let json = r#"{"is_final":false,"segment":2,"start_time":1.28,"text":"HELLO","timestamps":[0.4],"tokens":["HELLO"],"ys_probs":[-0.1],"lm_probs":[],"context_scores":[],"words":[]}"#;
+        let result: RecognizerJsonResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.text, "HELLO");
+        assert_eq!(result.segment, 2);
+        assert_eq!(result.start_time, 1.28);
+        assert_eq!(result.ys_probs, vec![-0.1]);
+        assert!(!result.is_eof);
+    }
+
+    #[test]
+    fn test_recognizer_json_result_word_ids() {
+        let json = r#"{"text":"HELLO","tokens":["HELLO"],"ys_probs":[-0.1],"words":[12,7]}"#;
+        let result: RecognizerJsonResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.words, vec![12, 7]);
+        assert_eq!(result.ys_probs, vec![-0.1]);
+    }
+
+    #[test]
+    fn test_words_from_tokens() {
+        let tokens = ["▁HE", "LLO", " ", "WORLD", "你", "好", "OK"].map(String::from);
+        let words = words_from_tokens(&tokens, &[0.1, 0.3, 0.5, 0.6, 1.0, 1.2, 1.4]);
+        let words: Vec<_> = words
+            .iter()
+            .map(|w| (w.word.as_str(), w.start, w.end))
+            .collect();
+        assert_eq!(
+            words,
+            [
+                ("HELLO", 0.1, 0.6),
+                ("WORLD", 0.6, 1.0),
+                ("你", 1.0, 1.2),
+                ("好", 1.2, 1.4),
+                ("OK", 1.4, 1.4),
+            ]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, asr::offline::AsrOfflineBaseConfig};
+
+/// NeMo Canary models, `use_pnc` is preset to true and `tgt_lang` follows `src_lang`,
+/// "en" when neither is set
+#[derive(Debug, FromBaseConfig)]
+pub struct CanaryAsrOfflineConfig {
+    #[base_config(path = "src/asr/offline/mod.rs")]
+    base: AsrOfflineBaseConfig,
+    encoder: Option<CString>,
+    decoder: Option<CString>,
+    src_lang: Option<CString>,
+    tgt_lang: Option<CString>,
+    // `tgt_lang` was set by `with_tgt_lang`
+    explicit_tgt_lang: bool,
+}
+
+impl Default for CanaryAsrOfflineConfig {
+    fn default() -> Self {
+        let mut config = Self {
+            base: AsrOfflineBaseConfig::default(),
+            encoder: None,
+            decoder: None,
+            src_lang: None,
+            tgt_lang: None,
+            explicit_tgt_lang: false,
+        };
+        config.with_use_pnc(true).set_tgt_lang("en");
+        config
+    }
+}
+
+impl CanaryAsrOfflineConfig {
+    pub fn with_encoder(&mut self, encoder: &str) -> &mut Self {
+        let encoder = as_c_string!(encoder);
+        self.base.config.model_config.canary.encoder = encoder.as_ptr();
+        self.encoder = Some(encoder);
+        self
+    }
+
+    pub fn with_decoder(&mut self, decoder: &str) -> &mut Self {
+        let decoder = as_c_string!(decoder);
+        self.base.config.model_config.canary.decoder = decoder.as_ptr();
+        self.decoder = Some(decoder);
+        self
+    }
+
+    /// language of the input audio, e.g. "en", "de", "es", "fr"
+    pub fn with_src_lang(&mut self, src_lang: &str) -> &mut Self {
+        if !self.explicit_tgt_lang {
+            self.set_tgt_lang(src_lang);
+        }
+        let src_lang = as_c_string!(src_lang);
+        self.base.config.model_config.canary.src_lang = src_lang.as_ptr();
+        self.src_lang = Some(src_lang);
+        self
+    }
+
+    /// language of the output text, set it different from `src_lang` to translate
+    pub fn with_tgt_lang(&mut self, tgt_lang: &str) -> &mut Self {
+        self.explicit_tgt_lang = true;
+        self.set_tgt_lang(tgt_lang)
+    }
+
+    /// output text with punctuation and casing
+    pub fn with_use_pnc(&mut self, use_pnc: bool) -> &mut Self {
+        self.base.config.model_config.canary.use_pnc = if use_pnc { 1 } else { 0 };
+        self
+    }
+}
+
+impl CanaryAsrOfflineConfig {
+    fn set_tgt_lang(&mut self, tgt_lang: &str) -> &mut Self {
+        let tgt_lang = as_c_string!(tgt_lang);
+        self.base.config.model_config.canary.tgt_lang = tgt_lang.as_ptr();
+        self.tgt_lang = Some(tgt_lang);
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig> for CanaryAsrOfflineConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig {
+        &self.base.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_canary_asr_offline_config_default() {
+        let config = CanaryAsrOfflineConfig::default();
+        let native: &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig = config.as_ref();
+        assert_eq!(native.model_config.canary.use_pnc, 1);
+        assert_eq!(
+            const_ptr_to_string!(native.model_config.canary.tgt_lang),
+            "en"
+        );
+
+        let mut config = CanaryAsrOfflineConfig::default();
+        config.with_src_lang("de");
+        let native: &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(native.model_config.canary.tgt_lang),
+            "de"
+        );
+    }
+
+    #[test]
+    fn test_canary_asr_offline_config() {
+        let mut config = CanaryAsrOfflineConfig::default();
+        config
+            .with_encoder("/assets/encoder.int8.onnx")
+            .with_decoder("/assets/decoder.int8.onnx")
+            .with_src_lang("en")
+            .with_tgt_lang("de")
+            .with_use_pnc(true)
+            .with_model_tokens("tokens.txt");
+        let config: &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.canary.encoder),
+            "/assets/encoder.int8.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.canary.decoder),
+            "/assets/decoder.int8.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.canary.src_lang),
+            "en"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.canary.tgt_lang),
+            "de"
+        );
+        assert_eq!(config.model_config.canary.use_pnc, 1);
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.tokens),
+            "tokens.txt"
+        );
+    }
+}
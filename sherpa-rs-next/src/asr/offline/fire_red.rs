@@ -0,0 +1,66 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, asr::offline::AsrOfflineBaseConfig};
+
+#[derive(Debug, Default, FromBaseConfig)]
+pub struct FireRedAsrOfflineConfig {
+    #[base_config(path = "src/asr/offline/mod.rs")]
+    base: AsrOfflineBaseConfig,
+    encoder: Option<CString>,
+    decoder: Option<CString>,
+}
+
+impl FireRedAsrOfflineConfig {
+    pub fn with_encoder(&mut self, encoder: &str) -> &mut Self {
+        let encoder = as_c_string!(encoder);
+        self.base.config.model_config.fire_red_asr.encoder = encoder.as_ptr();
+        self.encoder = Some(encoder);
+        self
+    }
+
+    pub fn with_decoder(&mut self, decoder: &str) -> &mut Self {
+        let decoder = as_c_string!(decoder);
+        self.base.config.model_config.fire_red_asr.decoder = decoder.as_ptr();
+        self.decoder = Some(decoder);
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig> for FireRedAsrOfflineConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig {
+        &self.base.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_fire_red_asr_offline_config() {
+        let mut config = FireRedAsrOfflineConfig::default();
+        config
+            .with_encoder("/assets/encoder.int8.onnx")
+            .with_decoder("/assets/decoder.int8.onnx")
+            .with_model_num_threads(2)
+            .with_model_tokens("tokens.txt");
+        let config: &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.fire_red_asr.encoder),
+            "/assets/encoder.int8.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.fire_red_asr.decoder),
+            "/assets/decoder.int8.onnx"
+        );
+        assert_eq!(config.model_config.num_threads, 2);
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.tokens),
+            "tokens.txt"
+        );
+    }
+}
@@ -1,10 +1,26 @@
 use std::cell::OnceCell;
 use std::ffi::CString;
 
-use crate::{as_c_string, asr::RecognizerJsonResult, const_ptr_to_string};
-
+use crate::{
+    as_c_string,
+    asr::{
+        RecognizerJsonResult,
+        offline::sense_voice::{AudioEvent, Emotion},
+    },
+    const_ptr_to_string,
+};
+
+pub mod canary;
+pub mod dolphin;
+pub mod fire_red;
+pub mod moonshine;
+pub mod nemo_ctc;
+pub mod nemo_transducer;
 pub mod paraformer;
 pub mod sense_voice;
+pub mod whisper;
+pub mod zipformer;
+pub mod zipformer_ctc;
 
 pub type AsrOfflineConfig = Box<dyn AsRef<sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig>>;
 
@@ -175,6 +191,8 @@ pub struct AsrOfflineResult {
     tokens: Vec<String>,
     text: String,
     lang: String,
+    emotion: String,
+    event: String,
     timestamps: Vec<f32>,
     json: String,
 }
@@ -196,6 +214,23 @@ impl AsrOfflineResult {
         &self.tokens
     }
 
+    /// emotion detected by SenseVoice, falls back to the inline tags of the text
+    pub fn emotion(&self) -> Option<Emotion> {
+        Emotion::from_tag(&self.emotion)
+            .or_else(|| sense_voice::inline_tags(&self.text).find_map(Emotion::from_tag))
+    }
+
+    /// audio event detected by SenseVoice, falls back to the inline tags of the text
+    pub fn event(&self) -> Option<AudioEvent> {
+        AudioEvent::from_tag(&self.event)
+            .or_else(|| sense_voice::inline_tags(&self.text).find_map(AudioEvent::from_tag))
+    }
+
+    /// text with inline tags like `<|HAPPY|>` removed
+    pub fn clean_text(&self) -> String {
+        sense_voice::strip_inline_tags(&self.text)
+    }
+
     pub fn json(&self) -> &anyhow::Result<RecognizerJsonResult> {
         let cached = self.json_value_cache.get_or_init(|| {
             serde_json::from_str(&self.json)
@@ -210,6 +245,8 @@ impl From<sherpa_rs_sys::SherpaOnnxOfflineRecognizerResult> for AsrOfflineResult
     fn from(result: sherpa_rs_sys::SherpaOnnxOfflineRecognizerResult) -> Self {
         let text_owned = const_ptr_to_string!(result.text, "".to_string());
         let lang_owned = const_ptr_to_string!(result.lang, "".to_string());
+        let emotion_owned = const_ptr_to_string!(result.emotion, "".to_string());
+        let event_owned = const_ptr_to_string!(result.event, "".to_string());
         let json_owned = const_ptr_to_string!(result.json, "".to_string());
         let timestamps_owned = if result.timestamps.is_null() || result.count == 0 {
             Vec::new()
@@ -233,13 +270,42 @@ impl From<sherpa_rs_sys::SherpaOnnxOfflineRecognizerResult> for AsrOfflineResult
             tokens: tokens_owned,
             text: text_owned,
             lang: lang_owned,
+            emotion: emotion_owned,
+            event: event_owned,
             timestamps: timestamps_owned,
             json: json_owned,
         }
     }
 }
 
-pub struct AsrOfflineRecognizer(*const sherpa_rs_sys::SherpaOnnxOfflineRecognizer);
+pub struct AsrOfflineRecognizer {
+    recognizer: *const sherpa_rs_sys::SherpaOnnxOfflineRecognizer,
+    // models like Canary do not report a language in the result,
+    // fall back to the configured output language
+    lang: String,
+    // model family whose options `update_config` can change
+    updatable: Option<UpdatableModel>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UpdatableModel {
+    Whisper,
+    Canary,
+}
+
+impl UpdatableModel {
+    fn of(config: &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig) -> Option<Self> {
+        if !const_ptr_to_string!(config.model_config.whisper.encoder, "".to_string()).is_empty() {
+            Some(Self::Whisper)
+        } else if !const_ptr_to_string!(config.model_config.canary.encoder, "".to_string())
+            .is_empty()
+        {
+            Some(Self::Canary)
+        } else {
+            None
+        }
+    }
+}
 
 impl AsrOfflineRecognizer {
     pub fn create_with_config(config: AsrOfflineConfig) -> anyhow::Result<Self> {
@@ -253,7 +319,59 @@ impl AsrOfflineRecognizer {
     {
         let config = config.as_ref();
         let recognizer = unsafe { sherpa_rs_sys::SherpaOnnxCreateOfflineRecognizer(config) };
-        Ok(Self(recognizer))
+        let lang = const_ptr_to_string!(config.model_config.canary.tgt_lang, "".to_string());
+        Ok(Self {
+            recognizer,
+            lang,
+            updatable: UpdatableModel::of(config),
+        })
+    }
+
+    /// Updates the options of a live recognizer without reloading the models.
+    /// Only Whisper (`language`, `task`) and Canary (`src_lang`, `tgt_lang`, `use_pnc`)
+    /// apply a new config, all other fields are ignored.
+    /// Fails for other models, whose decoder and hotwords graph are fixed at creation
+    pub fn update_config<T>(&mut self, config: T) -> anyhow::Result<()>
+    where
+        T: AsRef<sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig>,
+    {
+        let config = config.as_ref();
+        match self.updatable {
+            None => {
+                return Err(anyhow::anyhow!(
+                    "Only Whisper and Canary recognizers support update_config"
+                ));
+            }
+            Some(model) if UpdatableModel::of(config) != Some(model) => {
+                return Err(anyhow::anyhow!(
+                    "The new config is not a {:?} config",
+                    model
+                ));
+            }
+            Some(_) => {}
+        }
+        unsafe { sherpa_rs_sys::SherpaOnnxOfflineRecognizerSetConfig(self.recognizer, config) };
+        self.lang = const_ptr_to_string!(config.model_config.canary.tgt_lang, "".to_string());
+        Ok(())
+    }
+
+    /// Creates a stream decoded by this recognizer
+    pub fn create_stream(&self) -> anyhow::Result<OfflineStream<'_>> {
+        let stream = unsafe { sherpa_rs_sys::SherpaOnnxCreateOfflineStream(self.recognizer) };
+        OfflineStream::new(self, stream)
+    }
+
+    /// Creates a stream with its own hotwords on top of the global hotwords file,
+    /// hotwords are separated by `/`, e.g. "HELLO WORLD :2.0/GOOD MORNING"
+    pub fn create_stream_with_hotwords(&self, hotwords: &str) -> anyhow::Result<OfflineStream<'_>> {
+        let hotwords = as_c_string!(hotwords);
+        let stream = unsafe {
+            sherpa_rs_sys::SherpaOnnxCreateOfflineStreamWithHotwords(
+                self.recognizer,
+                hotwords.as_ptr(),
+            )
+        };
+        OfflineStream::new(self, stream)
     }
 
     pub fn transcribe(
@@ -261,25 +379,114 @@ impl AsrOfflineRecognizer {
         sample_rate: u32,
         samples: &[f32],
     ) -> anyhow::Result<AsrOfflineResult> {
+        let mut stream = self.create_stream()?;
+        stream.accept_waveform(sample_rate, samples);
+        stream.decode();
+        stream.result()
+    }
+
+    pub fn transcribe_with_hotwords(
+        &self,
+        sample_rate: u32,
+        samples: &[f32],
+        hotwords: &str,
+    ) -> anyhow::Result<AsrOfflineResult> {
+        let mut stream = self.create_stream_with_hotwords(hotwords)?;
+        stream.accept_waveform(sample_rate, samples);
+        stream.decode();
+        stream.result()
+    }
+}
+
+impl Drop for AsrOfflineRecognizer {
+    fn drop(&mut self) {
+        unsafe {
+            sherpa_rs_sys::SherpaOnnxDestroyOfflineRecognizer(self.recognizer);
+        }
+    }
+}
+
+/// A stream of audio decoded by a shared `AsrOfflineRecognizer`
+pub struct OfflineStream<'a> {
+    recognizer: &'a AsrOfflineRecognizer,
+    stream: *const sherpa_rs_sys::SherpaOnnxOfflineStream,
+}
+
+impl Drop for OfflineStream<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            sherpa_rs_sys::SherpaOnnxDestroyOfflineStream(self.stream);
+        }
+    }
+}
+
+impl<'a> OfflineStream<'a> {
+    fn new(
+        recognizer: &'a AsrOfflineRecognizer,
+        stream: *const sherpa_rs_sys::SherpaOnnxOfflineStream,
+    ) -> anyhow::Result<Self> {
+        if stream.is_null() {
+            return Err(anyhow::anyhow!("Failed to create offline stream"));
+        }
+        Ok(Self { recognizer, stream })
+    }
+
+    pub fn accept_waveform(&mut self, sample_rate: u32, samples: &[f32]) {
         unsafe {
-            let stream = sherpa_rs_sys::SherpaOnnxCreateOfflineStream(self.0);
             sherpa_rs_sys::SherpaOnnxAcceptWaveformOffline(
-                stream,
+                self.stream,
                 sample_rate as i32,
                 samples.as_ptr(),
                 samples.len() as i32,
             );
-            sherpa_rs_sys::SherpaOnnxDecodeOfflineStream(self.0, stream);
-            let result_ptr = sherpa_rs_sys::SherpaOnnxGetOfflineStreamResult(stream);
+        }
+    }
+
+    pub fn decode(&mut self) {
+        unsafe {
+            sherpa_rs_sys::SherpaOnnxDecodeOfflineStream(self.recognizer.recognizer, self.stream);
+        }
+    }
+
+    pub fn result(&self) -> anyhow::Result<AsrOfflineResult> {
+        unsafe {
+            let result_ptr = sherpa_rs_sys::SherpaOnnxGetOfflineStreamResult(self.stream);
             if result_ptr.is_null() {
-                sherpa_rs_sys::SherpaOnnxDestroyOfflineStream(stream);
                 return Err(anyhow::anyhow!("Failed to get offline stream result"));
             }
             let raw_result = result_ptr.read();
-            let result = AsrOfflineResult::from(raw_result);
+            let mut result = AsrOfflineResult::from(raw_result);
+            if result.lang.is_empty() {
+                result.lang = self.recognizer.lang.clone();
+            }
             sherpa_rs_sys::SherpaOnnxDestroyOfflineRecognizerResult(result_ptr);
-            sherpa_rs_sys::SherpaOnnxDestroyOfflineStream(stream);
             Ok(result)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_updatable_model() {
+        let mut whisper = whisper::WhisperAsrOfflineConfig::default();
+        whisper.with_encoder("encoder.onnx");
+        assert_eq!(
+            UpdatableModel::of(whisper.as_ref()),
+            Some(UpdatableModel::Whisper)
+        );
+
+        let mut canary = canary::CanaryAsrOfflineConfig::default();
+        canary.with_encoder("encoder.onnx");
+        assert_eq!(
+            UpdatableModel::of(canary.as_ref()),
+            Some(UpdatableModel::Canary)
+        );
+
+        let mut paraformer = paraformer::ParaformerAsrOfflineConfig::default();
+        paraformer.with_model("model.onnx");
+        assert_eq!(UpdatableModel::of(paraformer.as_ref()), None);
+    }
+}
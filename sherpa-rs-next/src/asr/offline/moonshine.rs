@@ -0,0 +1,51 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, asr::offline::AsrOfflineBaseConfig};
+
+#[derive(Debug, Default, FromBaseConfig)]
+pub struct MoonshineAsrOfflineConfig {
+    #[base_config(path = "src/asr/offline/mod.rs")]
+    base: AsrOfflineBaseConfig,
+    preprocessor: Option<CString>,
+    encoder: Option<CString>,
+    uncached_decoder: Option<CString>,
+    cached_decoder: Option<CString>,
+}
+
+impl MoonshineAsrOfflineConfig {
+    pub fn with_preprocessor(&mut self, preprocessor: &str) -> &mut Self {
+        let preprocessor = as_c_string!(preprocessor);
+        self.base.config.model_config.moonshine.preprocessor = preprocessor.as_ptr();
+        self.preprocessor = Some(preprocessor);
+        self
+    }
+
+    pub fn with_encoder(&mut self, encoder: &str) -> &mut Self {
+        let encoder = as_c_string!(encoder);
+        self.base.config.model_config.moonshine.encoder = encoder.as_ptr();
+        self.encoder = Some(encoder);
+        self
+    }
+
+    pub fn with_uncached_decoder(&mut self, uncached_decoder: &str) -> &mut Self {
+        let uncached_decoder = as_c_string!(uncached_decoder);
+        self.base.config.model_config.moonshine.uncached_decoder = uncached_decoder.as_ptr();
+        self.uncached_decoder = Some(uncached_decoder);
+        self
+    }
+
+    pub fn with_cached_decoder(&mut self, cached_decoder: &str) -> &mut Self {
+        let cached_decoder = as_c_string!(cached_decoder);
+        self.base.config.model_config.moonshine.cached_decoder = cached_decoder.as_ptr();
+        self.cached_decoder = Some(cached_decoder);
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig> for MoonshineAsrOfflineConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig {
+        &self.base.config
+    }
+}
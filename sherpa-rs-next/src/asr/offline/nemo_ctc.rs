@@ -0,0 +1,57 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, asr::offline::AsrOfflineBaseConfig};
+
+#[derive(Debug, Default, FromBaseConfig)]
+pub struct NemoCtcAsrOfflineConfig {
+    #[base_config(path = "src/asr/offline/mod.rs")]
+    base: AsrOfflineBaseConfig,
+    model: Option<CString>,
+}
+
+impl NemoCtcAsrOfflineConfig {
+    pub fn with_model(&mut self, model: &str) -> &mut Self {
+        let model = as_c_string!(model);
+        self.base.config.model_config.nemo_ctc.model = model.as_ptr();
+        self.model = Some(model);
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig> for NemoCtcAsrOfflineConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig {
+        &self.base.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_nemo_ctc_asr_offline_config() {
+        let mut config = NemoCtcAsrOfflineConfig::default();
+        config
+            .with_model("/assets/model.int8.onnx")
+            .with_model_debug(true)
+            .with_model_num_threads(4)
+            .with_model_provider("cpu")
+            .with_model_tokens("tokens.txt");
+        let config: &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.nemo_ctc.model),
+            "/assets/model.int8.onnx"
+        );
+        assert_eq!(config.model_config.debug, 1);
+        assert_eq!(config.model_config.num_threads, 4);
+        assert_eq!(const_ptr_to_string!(config.model_config.provider), "cpu");
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.tokens),
+            "tokens.txt"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, asr::offline::AsrOfflineBaseConfig};
+
+/// NeMo transducer models, e.g. Parakeet TDT
+/// `model_type` is preset to "nemo_transducer"
+#[derive(Debug, FromBaseConfig)]
+pub struct NemoTransducerAsrOfflineConfig {
+    #[base_config(path = "src/asr/offline/mod.rs")]
+    base: AsrOfflineBaseConfig,
+    encoder: Option<CString>,
+    decoder: Option<CString>,
+    joiner: Option<CString>,
+}
+
+impl Default for NemoTransducerAsrOfflineConfig {
+    fn default() -> Self {
+        let mut base = AsrOfflineBaseConfig::default();
+        base.with_model_type("nemo_transducer");
+        Self {
+            base,
+            encoder: None,
+            decoder: None,
+            joiner: None,
+        }
+    }
+}
+
+impl NemoTransducerAsrOfflineConfig {
+    pub fn with_encoder(&mut self, encoder: &str) -> &mut Self {
+        let encoder = as_c_string!(encoder);
+        self.base.config.model_config.transducer.encoder = encoder.as_ptr();
+        self.encoder = Some(encoder);
+        self
+    }
+
+    pub fn with_decoder(&mut self, decoder: &str) -> &mut Self {
+        let decoder = as_c_string!(decoder);
+        self.base.config.model_config.transducer.decoder = decoder.as_ptr();
+        self.decoder = Some(decoder);
+        self
+    }
+
+    pub fn with_joiner(&mut self, joiner: &str) -> &mut Self {
+        let joiner = as_c_string!(joiner);
+        self.base.config.model_config.transducer.joiner = joiner.as_ptr();
+        self.joiner = Some(joiner);
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig> for NemoTransducerAsrOfflineConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig {
+        &self.base.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_nemo_transducer_asr_offline_config() {
+        let mut config = NemoTransducerAsrOfflineConfig::default();
+        config
+            .with_encoder("/assets/encoder.int8.onnx")
+            .with_decoder("/assets/decoder.int8.onnx")
+            .with_joiner("/assets/joiner.int8.onnx")
+            .with_model_num_threads(2)
+            .with_model_tokens("tokens.txt");
+        let config: &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.transducer.encoder),
+            "/assets/encoder.int8.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.transducer.decoder),
+            "/assets/decoder.int8.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.transducer.joiner),
+            "/assets/joiner.int8.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.model_type),
+            "nemo_transducer"
+        );
+        assert_eq!(config.model_config.num_threads, 2);
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.tokens),
+            "tokens.txt"
+        );
+    }
+}
@@ -38,3 +38,136 @@ impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig> for SenseVoiceAsrOf
         &self.base.config
     }
 }
+
+/// Emotion tag emitted by SenseVoice, e.g. `<|HAPPY|>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emotion {
+    Neutral,
+    Happy,
+    Sad,
+    Angry,
+    Fearful,
+    Disgusted,
+    Surprised,
+    Unknown,
+}
+
+impl Emotion {
+    /// accepts both `<|HAPPY|>` and `HAPPY`
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let emotion = match strip_tag(tag).to_ascii_uppercase().as_str() {
+            "NEUTRAL" => Self::Neutral,
+            "HAPPY" => Self::Happy,
+            "SAD" => Self::Sad,
+            "ANGRY" => Self::Angry,
+            "FEARFUL" => Self::Fearful,
+            "DISGUSTED" => Self::Disgusted,
+            "SURPRISED" => Self::Surprised,
+            "EMO_UNKNOWN" => Self::Unknown,
+            _ => return None,
+        };
+        Some(emotion)
+    }
+}
+
+/// Audio event tag emitted by SenseVoice, e.g. `<|Laughter|>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioEvent {
+    Speech,
+    Bgm,
+    Applause,
+    Laughter,
+    Cry,
+    Sneeze,
+    Breath,
+    Cough,
+    Unknown,
+}
+
+impl AudioEvent {
+    /// accepts both `<|Laughter|>` and `Laughter`
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let event = match strip_tag(tag).to_ascii_uppercase().as_str() {
+            "SPEECH" => Self::Speech,
+            "BGM" => Self::Bgm,
+            "APPLAUSE" => Self::Applause,
+            "LAUGHTER" => Self::Laughter,
+            "CRY" => Self::Cry,
+            "SNEEZE" => Self::Sneeze,
+            "BREATH" => Self::Breath,
+            "COUGH" => Self::Cough,
+            "EVENT_UNK" => Self::Unknown,
+            _ => return None,
+        };
+        Some(event)
+    }
+}
+
+fn strip_tag(tag: &str) -> &str {
+    let tag = tag.trim();
+    tag.strip_prefix("<|")
+        .and_then(|t| t.strip_suffix("|>"))
+        .unwrap_or(tag)
+}
+
+/// iterates over the inline `<|...|>` tags of a text
+pub(crate) fn inline_tags(text: &str) -> impl Iterator<Item = &str> {
+    text.split("<|")
+        .skip(1)
+        .filter_map(|part| part.split_once("|>").map(|(tag, _)| tag))
+}
+
+/// removes the inline `<|...|>` tags of a text
+pub(crate) fn strip_inline_tags(text: &str) -> String {
+    let mut clean = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("<|") {
+        match rest[start..].find("|>") {
+            Some(end) => {
+                clean.push_str(&rest[..start]);
+                rest = &rest[start + end + 2..];
+                // one space is enough between the words around a tag
+                if clean.ends_with(char::is_whitespace) {
+                    rest = rest.trim_start();
+                }
+            }
+            None => break,
+        }
+    }
+    clean.push_str(rest);
+    clean.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sense_voice_tags() {
+        assert_eq!(Emotion::from_tag("<|HAPPY|>"), Some(Emotion::Happy));
+        assert_eq!(Emotion::from_tag("NEUTRAL"), Some(Emotion::Neutral));
+        assert_eq!(Emotion::from_tag("<|EMO_UNKNOWN|>"), Some(Emotion::Unknown));
+        assert_eq!(Emotion::from_tag("<|Speech|>"), None);
+        assert_eq!(AudioEvent::from_tag("<|BGM|>"), Some(AudioEvent::Bgm));
+        assert_eq!(
+            AudioEvent::from_tag("<|Laughter|>"),
+            Some(AudioEvent::Laughter)
+        );
+        assert_eq!(AudioEvent::from_tag(""), None);
+
+        let text = "<|en|><|HAPPY|><|Speech|><|withitn|>Hello world.";
+        assert_eq!(
+            inline_tags(text).collect::<Vec<_>>(),
+            vec!["en", "HAPPY", "Speech", "withitn"]
+        );
+        assert_eq!(strip_inline_tags(text), "Hello world.");
+        assert_eq!(strip_inline_tags("no tags"), "no tags");
+        assert_eq!(strip_inline_tags("Hello <|HAPPY|> world"), "Hello world");
+        assert_eq!(
+            strip_inline_tags("Hello <|HAPPY|><|BGM|>  world"),
+            "Hello world"
+        );
+        assert_eq!(strip_inline_tags("你好<|HAPPY|>世界"), "你好世界");
+        assert_eq!(strip_inline_tags("broken <|tag"), "broken <|tag");
+    }
+}
@@ -0,0 +1,103 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, asr::offline::AsrOfflineBaseConfig};
+
+#[derive(Debug, Default, FromBaseConfig)]
+pub struct WhisperAsrOfflineConfig {
+    #[base_config(path = "src/asr/offline/mod.rs")]
+    base: AsrOfflineBaseConfig,
+    encoder: Option<CString>,
+    decoder: Option<CString>,
+    language: Option<CString>,
+    task: Option<CString>,
+}
+
+impl WhisperAsrOfflineConfig {
+    pub fn with_encoder(&mut self, encoder: &str) -> &mut Self {
+        let encoder = as_c_string!(encoder);
+        self.base.config.model_config.whisper.encoder = encoder.as_ptr();
+        self.encoder = Some(encoder);
+        self
+    }
+
+    pub fn with_decoder(&mut self, decoder: &str) -> &mut Self {
+        let decoder = as_c_string!(decoder);
+        self.base.config.model_config.whisper.decoder = decoder.as_ptr();
+        self.decoder = Some(decoder);
+        self
+    }
+
+    /// language code such as "en", "zh", "de"
+    /// leave it empty to let the model detect the language,
+    /// the detected language is reported by `AsrOfflineResult::lang`
+    pub fn with_language(&mut self, language: &str) -> &mut Self {
+        let language = as_c_string!(language);
+        self.base.config.model_config.whisper.language = language.as_ptr();
+        self.language = Some(language);
+        self
+    }
+
+    /// task is either "transcribe" or "translate" (translate to English)
+    pub fn with_task(&mut self, task: &str) -> &mut Self {
+        let task = as_c_string!(task);
+        self.base.config.model_config.whisper.task = task.as_ptr();
+        self.task = Some(task);
+        self
+    }
+
+    pub fn with_tail_paddings(&mut self, tail_paddings: i32) -> &mut Self {
+        self.base.config.model_config.whisper.tail_paddings = tail_paddings;
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig> for WhisperAsrOfflineConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig {
+        &self.base.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_whisper_asr_offline_config() {
+        let mut config = WhisperAsrOfflineConfig::default();
+        config
+            .with_encoder("/assets/tiny-encoder.onnx")
+            .with_decoder("/assets/tiny-decoder.onnx")
+            .with_language("de")
+            .with_task("translate")
+            .with_tail_paddings(-1)
+            .with_model_num_threads(2)
+            .with_model_tokens("tokens.txt");
+        let config: &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.whisper.encoder),
+            "/assets/tiny-encoder.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.whisper.decoder),
+            "/assets/tiny-decoder.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.whisper.language),
+            "de"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.whisper.task),
+            "translate"
+        );
+        assert_eq!(config.model_config.whisper.tail_paddings, -1);
+        assert_eq!(config.model_config.num_threads, 2);
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.tokens),
+            "tokens.txt"
+        );
+    }
+}
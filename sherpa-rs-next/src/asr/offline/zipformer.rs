@@ -0,0 +1,88 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, asr::offline::AsrOfflineBaseConfig};
+
+#[derive(Debug, Default, FromBaseConfig)]
+pub struct ZipformerAsrOfflineConfig {
+    #[base_config(path = "src/asr/offline/mod.rs")]
+    base: AsrOfflineBaseConfig,
+    encoder: Option<CString>,
+    decoder: Option<CString>,
+    joiner: Option<CString>,
+}
+
+impl ZipformerAsrOfflineConfig {
+    pub fn with_encoder(&mut self, encoder: &str) -> &mut Self {
+        let encoder = as_c_string!(encoder);
+        self.base.config.model_config.transducer.encoder = encoder.as_ptr();
+        self.encoder = Some(encoder);
+        self
+    }
+
+    pub fn with_decoder(&mut self, decoder: &str) -> &mut Self {
+        let decoder = as_c_string!(decoder);
+        self.base.config.model_config.transducer.decoder = decoder.as_ptr();
+        self.decoder = Some(decoder);
+        self
+    }
+
+    pub fn with_joiner(&mut self, joiner: &str) -> &mut Self {
+        let joiner = as_c_string!(joiner);
+        self.base.config.model_config.transducer.joiner = joiner.as_ptr();
+        self.joiner = Some(joiner);
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig> for ZipformerAsrOfflineConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig {
+        &self.base.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_zipformer_asr_offline_config() {
+        let mut config = ZipformerAsrOfflineConfig::default();
+        config
+            .with_encoder("/assets/encoder.onnx")
+            .with_decoder("/assets/decoder.onnx")
+            .with_joiner("/assets/joiner.onnx")
+            .with_decoding_method("modified_beam_search")
+            .with_max_active_paths(4)
+            .with_hotwords_file("hotwords.txt")
+            .with_hotwords_score(2.0)
+            .with_model_tokens("tokens.txt");
+        let config: &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.transducer.encoder),
+            "/assets/encoder.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.transducer.decoder),
+            "/assets/decoder.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.transducer.joiner),
+            "/assets/joiner.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.decoding_method),
+            "modified_beam_search"
+        );
+        assert_eq!(config.max_active_paths, 4);
+        assert_eq!(const_ptr_to_string!(config.hotwords_file), "hotwords.txt");
+        assert_eq!(config.hotwords_score, 2.0);
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.tokens),
+            "tokens.txt"
+        );
+    }
+}
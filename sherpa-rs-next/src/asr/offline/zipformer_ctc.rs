@@ -0,0 +1,27 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, asr::offline::AsrOfflineBaseConfig};
+
+#[derive(Debug, Default, FromBaseConfig)]
+pub struct ZipformerCtcAsrOfflineConfig {
+    #[base_config(path = "src/asr/offline/mod.rs")]
+    base: AsrOfflineBaseConfig,
+    model: Option<CString>,
+}
+
+impl ZipformerCtcAsrOfflineConfig {
+    pub fn with_model(&mut self, model: &str) -> &mut Self {
+        let model = as_c_string!(model);
+        self.base.config.model_config.zipformer_ctc.model = model.as_ptr();
+        self.model = Some(model);
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig> for ZipformerCtcAsrOfflineConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineRecognizerConfig {
+        &self.base.config
+    }
+}
@@ -1,21 +1,42 @@
 use std::{cell::OnceCell, ffi::CString};
 
-use crate::{as_c_string, asr::RecognizerJsonResult, const_ptr_to_string};
+use crate::{
+    as_c_string,
+    asr::{RecognizerJsonResult, Word, words_from_tokens},
+    const_ptr_to_string,
+};
 
+pub mod nemo_ctc;
 pub mod paraformer;
+pub mod t_one_ctc;
 pub mod zipformer;
 pub mod zipformer_ctc;
 
 pub type AsrOnlineConfig = Box<dyn AsRef<sherpa_rs_sys::SherpaOnnxOnlineRecognizerConfig>>;
 
+// silence appended by `finish` so the last frames get decoded,
+// same as the sherpa-onnx examples
+const TAIL_PADDING_SECONDS: f32 = 0.66;
+
 #[derive(Debug, Default)]
 pub struct AsrOnlineBaseConfig {
     config: sherpa_rs_sys::SherpaOnnxOnlineRecognizerConfig,
 
     model_provider: Option<CString>,
     model_type: Option<CString>,
+    model_modeling_unit: Option<CString>,
+    model_bpe_vocab: Option<CString>,
     model_tokens: Option<CString>,
     decoding_method: Option<CString>,
+    hotwords_file: Option<CString>,
+    rule_fsts: Option<CString>,
+    rule_fars: Option<CString>,
+
+    hr_dict_dir: Option<CString>,
+    hr_lexicon: Option<CString>,
+    hr_rule_fsts: Option<CString>,
+
+    ctc_fst_decoder_graph: Option<CString>,
 }
 
 impl AsrOnlineBaseConfig {
@@ -43,6 +64,20 @@ impl AsrOnlineBaseConfig {
         self
     }
 
+    pub fn with_model_modeling_unit(&mut self, modeling_unit: &str) -> &mut Self {
+        let modeling_unit = as_c_string!(modeling_unit);
+        self.config.model_config.modeling_unit = modeling_unit.as_ptr();
+        self.model_modeling_unit = Some(modeling_unit);
+        self
+    }
+
+    pub fn with_model_bpe_vocab(&mut self, bpe_vocab: &str) -> &mut Self {
+        let bpe_vocab = as_c_string!(bpe_vocab);
+        self.config.model_config.bpe_vocab = bpe_vocab.as_ptr();
+        self.model_bpe_vocab = Some(bpe_vocab);
+        self
+    }
+
     pub fn with_model_tokens(&mut self, tokens: &str) -> &mut Self {
         let tokens = as_c_string!(tokens);
         self.config.model_config.tokens = tokens.as_ptr();
@@ -57,6 +92,11 @@ impl AsrOnlineBaseConfig {
         self
     }
 
+    pub fn with_max_active_paths(&mut self, max_active_paths: i32) -> &mut Self {
+        self.config.max_active_paths = max_active_paths;
+        self
+    }
+
     pub fn with_enable_endpoint(&mut self, enable_endpoint: bool) -> &mut Self {
         self.config.enable_endpoint = if enable_endpoint { 1 } else { 0 };
         self
@@ -85,10 +125,86 @@ impl AsrOnlineBaseConfig {
         self.config.rule3_min_utterance_length = rule3_min_utterance_length;
         self
     }
+
+    pub fn with_hotwords_file(&mut self, hotwords_file: &str) -> &mut Self {
+        let hotwords_file = as_c_string!(hotwords_file);
+        self.config.hotwords_file = hotwords_file.as_ptr();
+        self.hotwords_file = Some(hotwords_file);
+        self
+    }
+
+    pub fn with_hotwords_score(&mut self, hotwords_score: f32) -> &mut Self {
+        self.config.hotwords_score = hotwords_score;
+        self
+    }
+
+    pub fn with_rule_fsts(&mut self, rule_fsts: &str) -> &mut Self {
+        let rule_fsts = as_c_string!(rule_fsts);
+        self.config.rule_fsts = rule_fsts.as_ptr();
+        self.rule_fsts = Some(rule_fsts);
+        self
+    }
+
+    pub fn with_rule_fars(&mut self, rule_fars: &str) -> &mut Self {
+        let rule_fars = as_c_string!(rule_fars);
+        self.config.rule_fars = rule_fars.as_ptr();
+        self.rule_fars = Some(rule_fars);
+        self
+    }
+
+    pub fn with_blank_penalty(&mut self, blank_penalty: f32) -> &mut Self {
+        self.config.blank_penalty = blank_penalty;
+        self
+    }
+
+    pub fn with_hr_dict_dir(&mut self, hr_dict_dir: &str) -> &mut Self {
+        let hr_dict_dir = as_c_string!(hr_dict_dir);
+        self.config.hr.dict_dir = hr_dict_dir.as_ptr();
+        self.hr_dict_dir = Some(hr_dict_dir);
+        self
+    }
+
+    pub fn with_hr_lexicon(&mut self, hr_lexicon: &str) -> &mut Self {
+        let hr_lexicon = as_c_string!(hr_lexicon);
+        self.config.hr.lexicon = hr_lexicon.as_ptr();
+        self.hr_lexicon = Some(hr_lexicon);
+        self
+    }
+
+    pub fn with_hr_rule_fsts(&mut self, hr_rule_fsts: &str) -> &mut Self {
+        let hr_rule_fsts = as_c_string!(hr_rule_fsts);
+        self.config.hr.rule_fsts = hr_rule_fsts.as_ptr();
+        self.hr_rule_fsts = Some(hr_rule_fsts);
+        self
+    }
+
+    /// HLG decoding graph for CTC models, e.g. zipformer2 CTC
+    pub fn with_ctc_fst_decoder_graph(&mut self, graph: &str) -> &mut Self {
+        let graph = as_c_string!(graph);
+        self.config.ctc_fst_decoder_config.graph = graph.as_ptr();
+        self.ctc_fst_decoder_graph = Some(graph);
+        self
+    }
+
+    pub fn with_ctc_fst_decoder_max_active(&mut self, max_active: i32) -> &mut Self {
+        self.config.ctc_fst_decoder_config.max_active = max_active;
+        self
+    }
+
+    pub fn with_feat_sample_rate(&mut self, sample_rate: i32) -> &mut Self {
+        self.config.feat_config.sample_rate = sample_rate;
+        self
+    }
+
+    pub fn with_feat_feature_dim(&mut self, feature_dim: i32) -> &mut Self {
+        self.config.feat_config.feature_dim = feature_dim;
+        self
+    }
 }
 
 pub struct AsrOnlineResult {
     json_value_cache: OnceCell<anyhow::Result<RecognizerJsonResult>>,
+    words_cache: OnceCell<Vec<Word>>,
     tokens: Vec<String>,
     text: String,
     timestamps: Vec<f32>,
@@ -119,10 +235,34 @@ impl AsrOnlineResult {
         &self.tokens
     }
 
+    /// log probability of each token, empty if the model does not report it
+    pub fn token_probs(&self) -> &[f32] {
+        self.json()
+            .as_ref()
+            .map(|json| json.ys_probs.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// per-word timings in seconds since the segment started, derived from the tokens
+    /// and their timestamps, empty if the model does not report timestamps
+    pub fn words(&self) -> &[Word] {
+        self.words_cache
+            .get_or_init(|| words_from_tokens(&self.tokens, &self.timestamps))
+    }
+
+    /// start of the segment in seconds since the stream started
+    pub fn start_time(&self) -> f32 {
+        self.json()
+            .as_ref()
+            .map(|json| json.start_time)
+            .unwrap_or_default()
+    }
+
     pub fn is_final(&self) -> bool {
         self.is_final
     }
 
+    /// segment of the result, a final result carries the id of the segment it ends
     pub fn segment_id(&self) -> i32 {
         self.segment_id
     }
@@ -149,7 +289,8 @@ impl From<sherpa_rs_sys::SherpaOnnxOnlineRecognizerResult> for AsrOnlineResult {
         }
         Self {
             json_value_cache: OnceCell::new(),
-            tokens: Vec::new(),
+            words_cache: OnceCell::new(),
+            tokens: tokens_owned,
             text: text_owned,
             timestamps: timestamps_owned,
             json: json_owned,
@@ -163,6 +304,7 @@ pub struct AsrOnlineRecognizer {
     recognizer: *const sherpa_rs_sys::SherpaOnnxOnlineRecognizer,
     stream: *const sherpa_rs_sys::SherpaOnnxOnlineStream,
     segment_id: i32,
+    sample_rate: u32,
 }
 
 impl Drop for AsrOnlineRecognizer {
@@ -201,14 +343,134 @@ impl AsrOnlineRecognizer {
             recognizer,
             stream,
             segment_id: 0,
+            sample_rate: 16000,
         })
     }
 
+    /// Creates a new stream sharing the models of this recognizer
+    pub fn create_stream(&self) -> anyhow::Result<OnlineStream<'_>> {
+        OnlineStream::new(self, None)
+    }
+
+    /// Creates a stream with its own hotwords on top of the global hotwords file,
+    /// hotwords are separated by `/`, e.g. "HELLO WORLD :2.0/GOOD MORNING"
+    pub fn create_stream_with_hotwords(&self, hotwords: &str) -> anyhow::Result<OnlineStream<'_>> {
+        OnlineStream::new(self, Some(as_c_string!(hotwords)))
+    }
+
+    fn create_raw_stream(
+        &self,
+        hotwords: Option<&CString>,
+    ) -> anyhow::Result<*const sherpa_rs_sys::SherpaOnnxOnlineStream> {
+        let stream = unsafe {
+            match hotwords {
+                Some(hotwords) => sherpa_rs_sys::SherpaOnnxCreateOnlineStreamWithHotwords(
+                    self.recognizer,
+                    hotwords.as_ptr(),
+                ),
+                None => sherpa_rs_sys::SherpaOnnxCreateOnlineStream(self.recognizer),
+            }
+        };
+        if stream.is_null() {
+            return Err(anyhow::anyhow!("Failed to create online stream"));
+        }
+        Ok(stream)
+    }
+
+    /// Decodes all ready streams in batches until none of them is ready,
+    /// every stream must have been created by this recognizer
+    pub fn decode_streams(&self, streams: &[&mut OnlineStream]) -> anyhow::Result<()> {
+        if !streams.iter().all(|s| std::ptr::eq(s.recognizer, self)) {
+            return Err(anyhow::anyhow!(
+                "Stream was created by a different recognizer"
+            ));
+        }
+        loop {
+            let mut ready: Vec<*const sherpa_rs_sys::SherpaOnnxOnlineStream> = streams
+                .iter()
+                .filter(|s| s.is_ready())
+                .map(|s| s.stream)
+                .collect();
+            if ready.is_empty() {
+                break;
+            }
+            unsafe {
+                sherpa_rs_sys::SherpaOnnxDecodeMultipleOnlineStreams(
+                    self.recognizer,
+                    ready.as_mut_ptr(),
+                    ready.len() as i32,
+                );
+            }
+        }
+        Ok(())
+    }
+
     pub fn transcribe(
         &mut self,
         sample_rate: u32,
         samples: &[f32],
     ) -> anyhow::Result<AsrOnlineResult> {
+        self.sample_rate = sample_rate;
+        transcribe_stream(
+            self.recognizer,
+            self.stream,
+            &mut self.segment_id,
+            sample_rate,
+            samples,
+        )
+    }
+
+    /// Marks the end of input, pads and decodes the tail and returns the final result.
+    /// The recognizer starts a new segment afterwards and can be reused.
+    pub fn finish(&mut self) -> anyhow::Result<AsrOnlineResult> {
+        // the new stream is created first, so a failure leaves this one untouched
+        let stream = self.create_raw_stream(None)?;
+        let result = finish_stream(
+            self.recognizer,
+            self.stream,
+            self.segment_id,
+            self.sample_rate,
+        );
+        unsafe {
+            sherpa_rs_sys::SherpaOnnxDestroyOnlineStream(self.stream);
+        }
+        self.stream = stream;
+        self.segment_id += 1;
+        result
+    }
+}
+
+/// A stream of audio decoded by a shared `AsrOnlineRecognizer`
+pub struct OnlineStream<'a> {
+    recognizer: &'a AsrOnlineRecognizer,
+    stream: *const sherpa_rs_sys::SherpaOnnxOnlineStream,
+    hotwords: Option<CString>,
+    segment_id: i32,
+    sample_rate: u32,
+}
+
+impl Drop for OnlineStream<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            sherpa_rs_sys::SherpaOnnxDestroyOnlineStream(self.stream);
+        }
+    }
+}
+
+impl<'a> OnlineStream<'a> {
+    fn new(recognizer: &'a AsrOnlineRecognizer, hotwords: Option<CString>) -> anyhow::Result<Self> {
+        let stream = recognizer.create_raw_stream(hotwords.as_ref())?;
+        Ok(Self {
+            recognizer,
+            stream,
+            hotwords,
+            segment_id: 0,
+            sample_rate: 16000,
+        })
+    }
+
+    pub fn accept_waveform(&mut self, sample_rate: u32, samples: &[f32]) {
+        self.sample_rate = sample_rate;
         unsafe {
             sherpa_rs_sys::SherpaOnnxOnlineStreamAcceptWaveform(
                 self.stream,
@@ -216,25 +478,230 @@ impl AsrOnlineRecognizer {
                 samples.as_ptr(),
                 samples.len() as i32,
             );
+        }
+    }
 
-            while sherpa_rs_sys::SherpaOnnxIsOnlineStreamReady(self.recognizer, self.stream) == 1 {
-                sherpa_rs_sys::SherpaOnnxDecodeOnlineStream(self.recognizer, self.stream);
-            }
+    pub fn is_ready(&self) -> bool {
+        unsafe {
+            sherpa_rs_sys::SherpaOnnxIsOnlineStreamReady(self.recognizer.recognizer, self.stream)
+                == 1
+        }
+    }
 
-            let result_ptr =
-                sherpa_rs_sys::SherpaOnnxGetOnlineStreamResult(self.recognizer, self.stream);
-            let raw_result = result_ptr.read();
-            let mut result = AsrOnlineResult::from(raw_result);
-           
-            if sherpa_rs_sys::SherpaOnnxOnlineStreamIsEndpoint(self.recognizer, self.stream) == 1 {
-                self.segment_id += 1;
-                sherpa_rs_sys::SherpaOnnxOnlineStreamReset(self.recognizer, self.stream);
-                result.is_final = true;
+    /// Decodes until the stream is no longer ready,
+    /// use `AsrOnlineRecognizer::decode_streams` to decode many streams in a batch
+    pub fn decode(&mut self) {
+        while self.is_ready() {
+            unsafe {
+                sherpa_rs_sys::SherpaOnnxDecodeOnlineStream(
+                    self.recognizer.recognizer,
+                    self.stream,
+                );
             }
+        }
+    }
+
+    pub fn result(&self) -> anyhow::Result<AsrOnlineResult> {
+        let mut result = stream_result(self.recognizer.recognizer, self.stream)?;
+        result.segment_id = self.segment_id;
+        Ok(result)
+    }
+
+    pub fn is_endpoint(&self) -> bool {
+        unsafe {
+            sherpa_rs_sys::SherpaOnnxOnlineStreamIsEndpoint(self.recognizer.recognizer, self.stream)
+                == 1
+        }
+    }
+
+    /// Starts a new segment
+    pub fn reset(&mut self) {
+        unsafe {
+            sherpa_rs_sys::SherpaOnnxOnlineStreamReset(self.recognizer.recognizer, self.stream);
+        }
+        self.segment_id += 1;
+    }
+
+    pub fn segment_id(&self) -> i32 {
+        self.segment_id
+    }
+
+    /// Same as `AsrOnlineRecognizer::transcribe` but on this stream
+    pub fn transcribe(
+        &mut self,
+        sample_rate: u32,
+        samples: &[f32],
+    ) -> anyhow::Result<AsrOnlineResult> {
+        self.sample_rate = sample_rate;
+        transcribe_stream(
+            self.recognizer.recognizer,
+            self.stream,
+            &mut self.segment_id,
+            sample_rate,
+            samples,
+        )
+    }
+
+    /// Same as `AsrOnlineRecognizer::finish` but on this stream,
+    /// the per-stream hotwords are kept for the next segment
+    pub fn finish(&mut self) -> anyhow::Result<AsrOnlineResult> {
+        let stream = self.recognizer.create_raw_stream(self.hotwords.as_ref())?;
+        let result = finish_stream(
+            self.recognizer.recognizer,
+            self.stream,
+            self.segment_id,
+            self.sample_rate,
+        );
+        unsafe {
+            sherpa_rs_sys::SherpaOnnxDestroyOnlineStream(self.stream);
+        }
+        self.stream = stream;
+        self.segment_id += 1;
+        result
+    }
+}
+
+fn stream_result(
+    recognizer: *const sherpa_rs_sys::SherpaOnnxOnlineRecognizer,
+    stream: *const sherpa_rs_sys::SherpaOnnxOnlineStream,
+) -> anyhow::Result<AsrOnlineResult> {
+    unsafe {
+        let result_ptr = sherpa_rs_sys::SherpaOnnxGetOnlineStreamResult(recognizer, stream);
+        if result_ptr.is_null() {
+            return Err(anyhow::anyhow!("Failed to get online stream result"));
+        }
+        let raw_result = result_ptr.read();
+        let result = AsrOnlineResult::from(raw_result);
+        sherpa_rs_sys::SherpaOnnxDestroyOnlineRecognizerResult(result_ptr);
+        Ok(result)
+    }
+}
+
+fn transcribe_stream(
+    recognizer: *const sherpa_rs_sys::SherpaOnnxOnlineRecognizer,
+    stream: *const sherpa_rs_sys::SherpaOnnxOnlineStream,
+    segment_id: &mut i32,
+    sample_rate: u32,
+    samples: &[f32],
+) -> anyhow::Result<AsrOnlineResult> {
+    unsafe {
+        sherpa_rs_sys::SherpaOnnxOnlineStreamAcceptWaveform(
+            stream,
+            sample_rate as i32,
+            samples.as_ptr(),
+            samples.len() as i32,
+        );
+
+        while sherpa_rs_sys::SherpaOnnxIsOnlineStreamReady(recognizer, stream) == 1 {
+            sherpa_rs_sys::SherpaOnnxDecodeOnlineStream(recognizer, stream);
+        }
+
+        let mut result = stream_result(recognizer, stream)?;
+        let is_endpoint = sherpa_rs_sys::SherpaOnnxOnlineStreamIsEndpoint(recognizer, stream) == 1;
+        if is_endpoint {
+            sherpa_rs_sys::SherpaOnnxOnlineStreamReset(recognizer, stream);
+        }
+        number_segment(&mut result, segment_id, is_endpoint);
+
+        Ok(result)
+    }
+}
+
+/// The final result belongs to the segment it ends, the next result starts a new one
+fn number_segment(result: &mut AsrOnlineResult, segment_id: &mut i32, is_endpoint: bool) {
+    result.segment_id = *segment_id;
+    if is_endpoint {
+        result.is_final = true;
+        *segment_id += 1;
+    }
+}
+
+fn finish_stream(
+    recognizer: *const sherpa_rs_sys::SherpaOnnxOnlineRecognizer,
+    stream: *const sherpa_rs_sys::SherpaOnnxOnlineStream,
+    segment_id: i32,
+    sample_rate: u32,
+) -> anyhow::Result<AsrOnlineResult> {
+    let tail_paddings = vec![0.0; (sample_rate as f32 * TAIL_PADDING_SECONDS) as usize];
+    unsafe {
+        sherpa_rs_sys::SherpaOnnxOnlineStreamAcceptWaveform(
+            stream,
+            sample_rate as i32,
+            tail_paddings.as_ptr(),
+            tail_paddings.len() as i32,
+        );
+        sherpa_rs_sys::SherpaOnnxOnlineStreamInputFinished(stream);
+
+        while sherpa_rs_sys::SherpaOnnxIsOnlineStreamReady(recognizer, stream) == 1 {
+            sherpa_rs_sys::SherpaOnnxDecodeOnlineStream(recognizer, stream);
+        }
+    }
 
-            result.segment_id = self.segment_id;
-            sherpa_rs_sys::SherpaOnnxDestroyOnlineRecognizerResult(result_ptr);
-            Ok(result)
+    let mut result = stream_result(recognizer, stream)?;
+    result.segment_id = segment_id;
+    result.is_final = true;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(json: &str) -> AsrOnlineResult {
+        AsrOnlineResult {
+            json_value_cache: OnceCell::new(),
+            words_cache: OnceCell::new(),
+            tokens: Vec::new(),
+            text: String::new(),
+            timestamps: Vec::new(),
+            json: json.to_string(),
+            is_final: false,
+            segment_id: 0,
         }
     }
+
+    #[test]
+    fn test_segment_numbering() {
+        let mut segment_id = 0;
+
+        let mut partial = result("");
+        number_segment(&mut partial, &mut segment_id, false);
+        assert_eq!((partial.segment_id(), partial.is_final()), (0, false));
+
+        // the final result carries the id of the segment it ends
+        let mut last = result("");
+        number_segment(&mut last, &mut segment_id, true);
+        assert_eq!((last.segment_id(), last.is_final()), (0, true));
+
+        let mut next = result("");
+        number_segment(&mut next, &mut segment_id, false);
+        assert_eq!((next.segment_id(), next.is_final()), (1, false));
+    }
+
+    #[test]
+    fn test_result_accessors() {
+        // word ids of a decoding graph do not break the other fields
+        let mut r = result(
+            r#"{"text":" HELLO WORLD","tokens":[" HE","LLO"," WORLD"],"timestamps":[0.2,0.4,0.8],"ys_probs":[-0.1,-0.2,-0.3],"words":[5,9],"start_time":1.5,"segment":1}"#,
+        );
+        r.tokens = vec![" HE".into(), "LLO".into(), " WORLD".into()];
+        r.timestamps = vec![0.2, 0.4, 0.8];
+        assert_eq!(r.token_probs(), [-0.1, -0.2, -0.3]);
+        assert_eq!(r.start_time(), 1.5);
+
+        let words: Vec<_> = r
+            .words()
+            .iter()
+            .map(|w| (w.word.as_str(), w.start, w.end))
+            .collect();
+        assert_eq!(words, [("HELLO", 0.2, 0.8), ("WORLD", 0.8, 0.8)]);
+    }
+
+    #[test]
+    fn test_result_accessors_without_json() {
+        let r = result("");
+        assert!(r.token_probs().is_empty());
+        assert!(r.words().is_empty());
+        assert_eq!(r.start_time(), 0.0);
+    }
 }
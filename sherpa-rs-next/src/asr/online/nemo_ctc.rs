@@ -0,0 +1,61 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, asr::online::AsrOnlineBaseConfig};
+
+/// NeMo FastConformer cache-aware streaming CTC config
+#[derive(Debug, Default, FromBaseConfig)]
+pub struct NemoCtcAsrOnlineConfig {
+    #[base_config(path = "src/asr/online/mod.rs")]
+    base: AsrOnlineBaseConfig,
+
+    model: Option<CString>,
+}
+
+impl NemoCtcAsrOnlineConfig {
+    pub fn with_model(&mut self, model: &str) -> &mut Self {
+        let model = as_c_string!(model);
+        self.base.config.model_config.nemo_ctc.model = model.as_ptr();
+        self.model = Some(model);
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOnlineRecognizerConfig> for NemoCtcAsrOnlineConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOnlineRecognizerConfig {
+        &self.base.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_nemo_ctc_asr_online_config() {
+        let mut config = NemoCtcAsrOnlineConfig::default();
+        config
+            .with_model("/assets/model.onnx")
+            .with_model_tokens("tokens.txt")
+            .with_enable_endpoint(true)
+            .with_rule1_min_trailing_silence(2.4)
+            .with_rule2_min_trailing_silence(1.2)
+            .with_rule3_min_utterance_length(20.0);
+        let config: &sherpa_rs_sys::SherpaOnnxOnlineRecognizerConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.nemo_ctc.model),
+            "/assets/model.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.tokens),
+            "tokens.txt"
+        );
+        assert_eq!(config.enable_endpoint, 1);
+        assert_eq!(config.rule1_min_trailing_silence, 2.4);
+        assert_eq!(config.rule2_min_trailing_silence, 1.2);
+        assert_eq!(config.rule3_min_utterance_length, 20.0);
+    }
+}
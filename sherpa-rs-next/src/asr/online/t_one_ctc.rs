@@ -0,0 +1,61 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, asr::online::AsrOnlineBaseConfig};
+
+/// T-one streaming CTC config for 8 kHz telephony audio
+/// `feat_sample_rate` is preset to 8000
+#[derive(Debug, FromBaseConfig)]
+pub struct ToneCtcAsrOnlineConfig {
+    #[base_config(path = "src/asr/online/mod.rs")]
+    base: AsrOnlineBaseConfig,
+
+    model: Option<CString>,
+}
+
+impl Default for ToneCtcAsrOnlineConfig {
+    fn default() -> Self {
+        let mut base = AsrOnlineBaseConfig::default();
+        base.with_feat_sample_rate(8000);
+        Self { base, model: None }
+    }
+}
+
+impl ToneCtcAsrOnlineConfig {
+    pub fn with_model(&mut self, model: &str) -> &mut Self {
+        let model = as_c_string!(model);
+        self.base.config.model_config.t_one_ctc.model = model.as_ptr();
+        self.model = Some(model);
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOnlineRecognizerConfig> for ToneCtcAsrOnlineConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOnlineRecognizerConfig {
+        &self.base.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_t_one_ctc_asr_online_config() {
+        let mut config = ToneCtcAsrOnlineConfig::default();
+        config
+            .with_model("/assets/model.onnx")
+            .with_model_tokens("tokens.txt")
+            .with_enable_endpoint(true);
+        let config: &sherpa_rs_sys::SherpaOnnxOnlineRecognizerConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.t_one_ctc.model),
+            "/assets/model.onnx"
+        );
+        assert_eq!(config.feat_config.sample_rate, 8000);
+        assert_eq!(config.enable_endpoint, 1);
+    }
+}
@@ -42,3 +42,53 @@ impl AsRef<sherpa_rs_sys::SherpaOnnxOnlineRecognizerConfig> for ZipformerAsrOnli
         &self.base.config
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_zipformer_asr_online_config() {
+        let mut config = ZipformerAsrOnlineConfig::default();
+        config
+            .with_encoder("/assets/encoder.onnx")
+            .with_decoder("/assets/decoder.onnx")
+            .with_joiner("/assets/joiner.onnx")
+            .with_decoding_method("modified_beam_search")
+            .with_max_active_paths(4)
+            .with_hotwords_file("hotwords.txt")
+            .with_hotwords_score(1.5)
+            .with_model_modeling_unit("cjkchar+bpe")
+            .with_model_bpe_vocab("bpe.vocab")
+            .with_rule_fsts("itn.fst")
+            .with_blank_penalty(0.5)
+            .with_hr_lexicon("lexicon.txt")
+            .with_feat_sample_rate(16000);
+        let config: &sherpa_rs_sys::SherpaOnnxOnlineRecognizerConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.transducer.encoder),
+            "/assets/encoder.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.decoding_method),
+            "modified_beam_search"
+        );
+        assert_eq!(config.max_active_paths, 4);
+        assert_eq!(const_ptr_to_string!(config.hotwords_file), "hotwords.txt");
+        assert_eq!(config.hotwords_score, 1.5);
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.modeling_unit),
+            "cjkchar+bpe"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model_config.bpe_vocab),
+            "bpe.vocab"
+        );
+        assert_eq!(const_ptr_to_string!(config.rule_fsts), "itn.fst");
+        assert_eq!(config.blank_penalty, 0.5);
+        assert_eq!(const_ptr_to_string!(config.hr.lexicon), "lexicon.txt");
+        assert_eq!(config.feat_config.sample_rate, 16000);
+    }
+}
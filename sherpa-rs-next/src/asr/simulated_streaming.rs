@@ -0,0 +1,318 @@
+use crate::{
+    asr::offline::{AsrOfflineRecognizer, AsrOfflineResult},
+    vad::Vad,
+};
+
+/// Pseudo-streaming recognition with an offline model:
+/// the VAD gates the audio, the current segment is decoded again
+/// every `partial_interval` seconds and once more when the VAD closes it
+pub struct SimulatedStreamingRecognizer {
+    vad: Vad,
+    recognizer: AsrOfflineRecognizer,
+    sample_rate: u32,
+    window_size: usize,
+    // seconds
+    partial_interval: f32,
+    pre_roll: f32,
+
+    buffer: SpeechBuffer,
+    last_text: String,
+    segment_id: i32,
+}
+
+pub struct SimulatedStreamingResult {
+    result: AsrOfflineResult,
+    is_final: bool,
+    segment_id: i32,
+}
+
+impl SimulatedStreamingResult {
+    pub fn text(&self) -> &str {
+        self.result.text()
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.is_final
+    }
+
+    pub fn segment_id(&self) -> i32 {
+        self.segment_id
+    }
+
+    pub fn result(&self) -> &AsrOfflineResult {
+        &self.result
+    }
+}
+
+impl SimulatedStreamingRecognizer {
+    /// `vad` should be configured with the same sample rate, 16000 by default
+    pub fn new(mut vad: Vad, recognizer: AsrOfflineRecognizer) -> Self {
+        vad.reset();
+        Self {
+            vad,
+            recognizer,
+            sample_rate: 16000,
+            window_size: 512,
+            partial_interval: 0.2,
+            pre_roll: 0.3,
+            buffer: SpeechBuffer::default(),
+            last_text: String::new(),
+            segment_id: 0,
+        }
+    }
+
+    pub fn with_sample_rate(&mut self, sample_rate: u32) -> &mut Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// samples fed to the VAD at once, e.g. 512 for silero and 256 for ten-vad
+    pub fn with_window_size(&mut self, window_size: usize) -> &mut Self {
+        self.window_size = window_size;
+        self
+    }
+
+    /// seconds of new audio between two partial decodes
+    pub fn with_partial_interval(&mut self, seconds: f32) -> &mut Self {
+        self.partial_interval = seconds;
+        self
+    }
+
+    /// seconds of audio kept before the VAD detects speech
+    pub fn with_pre_roll(&mut self, seconds: f32) -> &mut Self {
+        self.pre_roll = seconds;
+        self
+    }
+
+    /// Pushes audio at the configured sample rate,
+    /// returns the partial results and the finished segments
+    pub fn accept_waveform(
+        &mut self,
+        samples: &[f32],
+    ) -> anyhow::Result<Vec<SimulatedStreamingResult>> {
+        let mut results = Vec::new();
+        let partial_interval = self.seconds_to_samples(self.partial_interval);
+        let pre_roll = self.seconds_to_samples(self.pre_roll);
+        self.buffer.extend(samples);
+
+        while let Some(window) = self.buffer.next_window(self.window_size) {
+            self.vad.accept_waveform(window.to_vec());
+            if self.vad.is_speech() {
+                self.buffer.start_speech(pre_roll);
+            }
+            self.buffer.advance(self.window_size);
+
+            self.pop_segments(&mut results)?;
+        }
+
+        if self.buffer.in_speech() {
+            if let Some(speech) = self.buffer.take_partial(partial_interval) {
+                let result = self.recognizer.transcribe(self.sample_rate, speech)?;
+                if !result.text().is_empty() && result.text() != self.last_text {
+                    self.last_text = result.text().to_string();
+                    results.push(SimulatedStreamingResult {
+                        result,
+                        is_final: false,
+                        segment_id: self.segment_id,
+                    });
+                }
+            }
+        } else {
+            self.buffer.trim(pre_roll);
+        }
+
+        Ok(results)
+    }
+
+    /// Closes the current segment at the end of input
+    pub fn flush(&mut self) -> anyhow::Result<Vec<SimulatedStreamingResult>> {
+        let mut results = Vec::new();
+        self.vad.flush();
+        self.pop_segments(&mut results)?;
+        self.vad.reset();
+        self.buffer = SpeechBuffer::default();
+        self.last_text.clear();
+        Ok(results)
+    }
+
+    fn pop_segments(&mut self, results: &mut Vec<SimulatedStreamingResult>) -> anyhow::Result<()> {
+        while !self.vad.is_empty() {
+            let segment = self.vad.front();
+            self.vad.pop();
+
+            let result = self
+                .recognizer
+                .transcribe(self.sample_rate, &segment.samples)?;
+            results.push(SimulatedStreamingResult {
+                result,
+                is_final: true,
+                segment_id: self.segment_id,
+            });
+
+            self.buffer.end_segment();
+            self.last_text.clear();
+            self.segment_id += 1;
+        }
+        Ok(())
+    }
+
+    fn seconds_to_samples(&self, seconds: f32) -> usize {
+        (seconds * self.sample_rate as f32) as usize
+    }
+}
+
+/// Audio kept for decoding, `samples[..offset]` has been fed to the VAD
+#[derive(Debug, Default)]
+struct SpeechBuffer {
+    samples: Vec<f32>,
+    offset: usize,
+    // start of the current speech in `samples`
+    speech_start: Option<usize>,
+    // `offset` at the last partial decode
+    last_decode: usize,
+}
+
+impl SpeechBuffer {
+    fn extend(&mut self, samples: &[f32]) {
+        self.samples.extend_from_slice(samples);
+    }
+
+    /// next window not yet fed to the VAD
+    fn next_window(&self, window_size: usize) -> Option<&[f32]> {
+        self.samples.get(self.offset..self.offset + window_size)
+    }
+
+    fn advance(&mut self, window_size: usize) {
+        self.offset += window_size;
+    }
+
+    fn in_speech(&self) -> bool {
+        self.speech_start.is_some()
+    }
+
+    /// marks speech from the current window on, with `pre_roll` samples before it
+    fn start_speech(&mut self, pre_roll: usize) {
+        if self.speech_start.is_none() {
+            self.speech_start = Some(self.offset.saturating_sub(pre_roll));
+            self.last_decode = self.offset;
+        }
+    }
+
+    /// speech so far once `interval` samples arrived since the last partial decode
+    fn take_partial(&mut self, interval: usize) -> Option<&[f32]> {
+        let start = self.speech_start?;
+        if self.offset - self.last_decode < interval {
+            return None;
+        }
+        self.last_decode = self.offset;
+        Some(&self.samples[start..self.offset])
+    }
+
+    /// everything fed so far belongs to the finished segment
+    fn end_segment(&mut self) {
+        self.drop_front(self.offset);
+        self.speech_start = None;
+    }
+
+    /// keeps only the pre-roll while there is no speech
+    fn trim(&mut self, pre_roll: usize) {
+        if self.speech_start.is_none() {
+            self.drop_front(self.offset.saturating_sub(pre_roll));
+        }
+    }
+
+    fn drop_front(&mut self, n: usize) {
+        self.samples.drain(..n);
+        self.offset -= n;
+        self.last_decode = self.last_decode.saturating_sub(n);
+        if let Some(start) = self.speech_start.as_mut() {
+            *start = start.saturating_sub(n);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(start: usize, len: usize) -> Vec<f32> {
+        (start..start + len).map(|i| i as f32).collect()
+    }
+
+    // feeds every full window with the same VAD decision
+    fn feed(buffer: &mut SpeechBuffer, window: usize, pre_roll: usize, is_speech: bool) -> usize {
+        let mut windows = 0;
+        while buffer.next_window(window).is_some() {
+            if is_speech {
+                buffer.start_speech(pre_roll);
+            }
+            buffer.advance(window);
+            windows += 1;
+        }
+        windows
+    }
+
+    #[test]
+    fn test_trim_before_speech() {
+        let mut buffer = SpeechBuffer::default();
+        buffer.extend(&ramp(0, 10));
+        assert_eq!(feed(&mut buffer, 4, 3, false), 2);
+        buffer.trim(3);
+
+        // the 3 samples before the offset and the 2 not yet fed are kept
+        assert_eq!(buffer.samples, ramp(5, 5));
+        assert_eq!(buffer.offset, 3);
+        assert_eq!(buffer.next_window(4), None);
+
+        buffer.extend(&ramp(10, 2));
+        assert_eq!(buffer.next_window(4), Some(&ramp(8, 4)[..]));
+    }
+
+    #[test]
+    fn test_speech_start_with_pre_roll() {
+        let mut buffer = SpeechBuffer::default();
+        buffer.extend(&ramp(0, 8));
+        feed(&mut buffer, 4, 3, false);
+        buffer.trim(3);
+        assert_eq!(buffer.samples, ramp(5, 3));
+
+        buffer.extend(&ramp(8, 4));
+        feed(&mut buffer, 4, 3, true);
+        assert!(buffer.in_speech());
+
+        // speech starts at sample 8, with 3 samples of pre-roll
+        assert_eq!(buffer.take_partial(4), Some(&ramp(5, 7)[..]));
+        // nothing new since the last partial
+        assert_eq!(buffer.take_partial(4), None);
+
+        // trimming is a no-op during speech
+        buffer.trim(0);
+        buffer.extend(&ramp(12, 4));
+        feed(&mut buffer, 4, 3, true);
+        assert_eq!(buffer.take_partial(4), Some(&ramp(5, 11)[..]));
+    }
+
+    #[test]
+    fn test_segment_ends_mid_chunk() {
+        let mut buffer = SpeechBuffer::default();
+        buffer.extend(&ramp(0, 10));
+
+        buffer.start_speech(0);
+        buffer.advance(4);
+        buffer.advance(4);
+        // the VAD closes the segment after the second window
+        buffer.end_segment();
+
+        assert!(!buffer.in_speech());
+        assert_eq!(buffer.offset, 0);
+        assert_eq!(buffer.last_decode, 0);
+        // the tail of the chunk is kept for the next window
+        assert_eq!(buffer.samples, ramp(8, 2));
+
+        buffer.extend(&ramp(10, 2));
+        assert_eq!(buffer.next_window(4), Some(&ramp(8, 4)[..]));
+        buffer.start_speech(2);
+        buffer.advance(4);
+        assert_eq!(buffer.take_partial(4), Some(&ramp(8, 4)[..]));
+    }
+}
@@ -0,0 +1,384 @@
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use crate::asr::{
+    online::AsrOnlineResult, simulated_streaming::SimulatedStreamingResult, two_pass::TwoPassResult,
+};
+
+/// A partial or final hypothesis of a streaming recognizer
+pub trait StreamingHypothesis {
+    fn tokens(&self) -> &[String];
+    fn is_final(&self) -> bool;
+    fn segment_id(&self) -> i32;
+}
+
+impl StreamingHypothesis for AsrOnlineResult {
+    fn tokens(&self) -> &[String] {
+        AsrOnlineResult::tokens(self)
+    }
+
+    fn is_final(&self) -> bool {
+        AsrOnlineResult::is_final(self)
+    }
+
+    fn segment_id(&self) -> i32 {
+        AsrOnlineResult::segment_id(self)
+    }
+}
+
+impl StreamingHypothesis for SimulatedStreamingResult {
+    fn tokens(&self) -> &[String] {
+        self.result().tokens()
+    }
+
+    fn is_final(&self) -> bool {
+        SimulatedStreamingResult::is_final(self)
+    }
+
+    fn segment_id(&self) -> i32 {
+        SimulatedStreamingResult::segment_id(self)
+    }
+}
+
+impl StreamingHypothesis for TwoPassResult {
+    fn tokens(&self) -> &[String] {
+        match self.offline() {
+            Some(offline) => offline.tokens(),
+            None => self.online().tokens(),
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        TwoPassResult::is_final(self)
+    }
+
+    fn segment_id(&self) -> i32 {
+        TwoPassResult::segment_id(self)
+    }
+}
+
+/// Splits partial hypotheses into a stable prefix and an unstable tail.
+/// A token is stable once it and every token before it stayed unchanged
+/// for `min_updates` further updates or for `min_duration`.
+/// The stable text never shrinks within a segment, later hypotheses
+/// only replace the text after it, even if they split it into other tokens.
+/// A final hypothesis is passed through unchanged
+pub struct PartialStabilizer {
+    min_updates: usize,
+    min_duration: Duration,
+    segment_id: Option<i32>,
+    // tokens of the last hypothesis, with the update index and time they appeared at
+    tokens: Vec<(String, usize, Instant)>,
+    // tokens already reported as stable
+    stable: Vec<String>,
+    updates: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StabilizedResult {
+    tokens: Vec<String>,
+    stable_len: usize,
+    is_final: bool,
+    segment_id: i32,
+}
+
+impl StabilizedResult {
+    /// tokens reported stable before, followed by the tail of the latest hypothesis
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+
+    pub fn stable_tokens(&self) -> Range<usize> {
+        0..self.stable_len
+    }
+
+    pub fn unstable_tokens(&self) -> Range<usize> {
+        self.stable_len..self.tokens.len()
+    }
+
+    /// stable tokens joined, the tail may still be rewritten
+    pub fn stable_text(&self) -> String {
+        self.tokens[self.stable_tokens()].concat()
+    }
+
+    pub fn unstable_text(&self) -> String {
+        self.tokens[self.unstable_tokens()].concat()
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.is_final
+    }
+
+    pub fn segment_id(&self) -> i32 {
+        self.segment_id
+    }
+}
+
+impl Default for PartialStabilizer {
+    fn default() -> Self {
+        Self {
+            min_updates: 2,
+            min_duration: Duration::from_millis(600),
+            segment_id: None,
+            tokens: Vec::new(),
+            stable: Vec::new(),
+            updates: 0,
+        }
+    }
+}
+
+impl PartialStabilizer {
+    pub fn with_min_updates(&mut self, min_updates: usize) -> &mut Self {
+        self.min_updates = min_updates;
+        self
+    }
+
+    pub fn with_min_duration(&mut self, min_duration: Duration) -> &mut Self {
+        self.min_duration = min_duration;
+        self
+    }
+
+    pub fn update<T: StreamingHypothesis>(&mut self, result: &T) -> StabilizedResult {
+        self.update_at(result, Instant::now())
+    }
+
+    /// same as `update` with the time the result was received
+    pub fn update_at<T: StreamingHypothesis>(
+        &mut self,
+        result: &T,
+        now: Instant,
+    ) -> StabilizedResult {
+        let segment_id = result.segment_id();
+        if self.segment_id != Some(segment_id) {
+            self.reset();
+            self.segment_id = Some(segment_id);
+        }
+        self.updates += 1;
+
+        let hypothesis = result.tokens();
+        if result.is_final() {
+            self.reset();
+            return StabilizedResult {
+                tokens: hypothesis.to_vec(),
+                stable_len: hypothesis.len(),
+                is_final: true,
+                segment_id,
+            };
+        }
+
+        let common = self
+            .tokens
+            .iter()
+            .zip(hypothesis)
+            .take_while(|((previous, _, _), token)| previous == *token)
+            .count();
+        self.tokens.truncate(common);
+        self.tokens.extend(
+            hypothesis[common..]
+                .iter()
+                .map(|token| (token.clone(), self.updates, now)),
+        );
+
+        // tokens appear in order, so the first unstable token ends the prefix
+        let aged = self
+            .tokens
+            .iter()
+            .take_while(|(_, update, time)| {
+                self.updates - update >= self.min_updates
+                    || now.duration_since(*time) >= self.min_duration
+            })
+            .count();
+
+        let stable_text = self.stable.concat();
+        let hypothesis_text = hypothesis.concat();
+        let aged_text = hypothesis[..aged].concat();
+        let (stable, tail) =
+            if aged_text.len() > stable_text.len() && aged_text.starts_with(&stable_text) {
+                (hypothesis[..aged].to_vec(), hypothesis[aged..].to_vec())
+            } else if hypothesis_text.starts_with(&stable_text) {
+                split_at_text(hypothesis, stable_text.len())
+            } else {
+                // the hypothesis rewrote the stable text, keep it and
+                // replace only what comes after the same amount of text
+                let mut covered = 0;
+                let skip = hypothesis
+                    .iter()
+                    .take_while(|token| {
+                        let skip = covered < stable_text.len();
+                        covered += token.len();
+                        skip
+                    })
+                    .count();
+                (self.stable.clone(), hypothesis[skip..].to_vec())
+            };
+        self.stable = stable;
+
+        StabilizedResult {
+            stable_len: self.stable.len(),
+            tokens: self.stable.iter().chain(&tail).cloned().collect(),
+            is_final: false,
+            segment_id,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.segment_id = None;
+        self.tokens.clear();
+        self.stable.clear();
+        self.updates = 0;
+    }
+}
+
+/// Splits tokens after the first `len` bytes of their text,
+/// cutting the token that crosses the boundary in two
+fn split_at_text(tokens: &[String], len: usize) -> (Vec<String>, Vec<String>) {
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+    let mut offset = 0;
+    for token in tokens {
+        if offset >= len {
+            tail.push(token.clone());
+        } else if offset + token.len() <= len {
+            head.push(token.clone());
+        } else {
+            let (stable, rest) = token.split_at(len - offset);
+            head.push(stable.to_string());
+            tail.push(rest.to_string());
+        }
+        offset += token.len();
+    }
+    (head, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Hypothesis(Vec<String>, bool, i32);
+
+    impl StreamingHypothesis for Hypothesis {
+        fn tokens(&self) -> &[String] {
+            &self.0
+        }
+
+        fn is_final(&self) -> bool {
+            self.1
+        }
+
+        fn segment_id(&self) -> i32 {
+            self.2
+        }
+    }
+
+    fn partial(tokens: &[&str]) -> Hypothesis {
+        Hypothesis(tokens.iter().map(|t| t.to_string()).collect(), false, 0)
+    }
+
+    fn final_(tokens: &[&str]) -> Hypothesis {
+        Hypothesis(tokens.iter().map(|t| t.to_string()).collect(), true, 0)
+    }
+
+    #[test]
+    fn test_stable_after_updates() {
+        let mut stabilizer = PartialStabilizer::default();
+        stabilizer
+            .with_min_updates(2)
+            .with_min_duration(Duration::from_secs(60));
+        let now = Instant::now();
+
+        let r = stabilizer.update_at(&partial(&["he", "llo"]), now);
+        assert_eq!(r.stable_tokens(), 0..0);
+        let r = stabilizer.update_at(&partial(&["he", "llo", " wor"]), now);
+        assert_eq!(r.stable_tokens(), 0..0);
+        let r = stabilizer.update_at(&partial(&["he", "llo", " world"]), now);
+        assert_eq!(r.stable_text(), "hello");
+        assert_eq!(r.unstable_text(), " world");
+        assert_eq!(r.unstable_tokens(), 2..3);
+
+        // a rewrite of stable tokens is ignored, only the tail follows the hypothesis
+        let r = stabilizer.update_at(&partial(&["ha", "llo", " word", "s"]), now);
+        assert_eq!(r.stable_text(), "hello");
+        assert_eq!(r.unstable_text(), " words");
+
+        // a shorter hypothesis keeps the stable tokens too
+        let r = stabilizer.update_at(&partial(&["he"]), now);
+        assert_eq!(r.stable_text(), "hello");
+        assert_eq!(r.unstable_tokens(), 2..2);
+    }
+
+    #[test]
+    fn test_stable_after_duration() {
+        let mut stabilizer = PartialStabilizer::default();
+        stabilizer
+            .with_min_updates(100)
+            .with_min_duration(Duration::from_millis(500));
+        let now = Instant::now();
+
+        stabilizer.update_at(&partial(&["a", "b"]), now);
+        let r = stabilizer.update_at(&partial(&["a", "b", "c"]), now + Duration::from_millis(600));
+        assert_eq!(r.stable_text(), "ab");
+        assert_eq!(r.unstable_text(), "c");
+    }
+
+    #[test]
+    fn test_resplit_tokens() {
+        let mut stabilizer = PartialStabilizer::default();
+        stabilizer
+            .with_min_updates(1)
+            .with_min_duration(Duration::from_secs(60));
+        let now = Instant::now();
+
+        stabilizer.update_at(&partial(&["he", "llo"]), now);
+        let r = stabilizer.update_at(&partial(&["he", "llo"]), now);
+        assert_eq!(r.stable_text(), "hello");
+
+        // the same text split into other tokens, nothing after the stable text is dropped
+        let r = stabilizer.update_at(&partial(&["hello", " world", " again"]), now);
+        assert_eq!(r.stable_text(), "hello");
+        assert_eq!(r.unstable_text(), " world again");
+        assert_eq!(r.tokens().concat(), "hello world again");
+
+        // a token across the end of the stable text is cut in two
+        let r = stabilizer.update_at(&partial(&["hel", "lo w", "orld"]), now);
+        assert_eq!(r.tokens(), ["hel", "lo", " w", "orld"]);
+        assert_eq!(r.stable_text(), "hello");
+        assert_eq!(r.unstable_text(), " world");
+    }
+
+    #[test]
+    fn test_final_and_new_segment() {
+        let mut stabilizer = PartialStabilizer::default();
+        let now = Instant::now();
+
+        stabilizer.update_at(&partial(&["a", "b"]), now);
+        let r = stabilizer.update_at(&Hypothesis(vec!["a".into(), "c".into()], true, 0), now);
+        assert!(r.is_final());
+        assert_eq!(r.stable_text(), "ac");
+        assert_eq!(r.unstable_text(), "");
+
+        let r = stabilizer.update_at(&Hypothesis(vec!["a".into()], false, 1), now);
+        assert_eq!(r.segment_id(), 1);
+        assert_eq!(r.stable_tokens(), 0..0);
+    }
+
+    #[test]
+    fn test_two_pass_final() {
+        let mut stabilizer = PartialStabilizer::default();
+        stabilizer.with_min_updates(1);
+        let now = Instant::now();
+
+        stabilizer.update_at(&partial(&["he", "llo", " wor"]), now);
+        let r = stabilizer.update_at(&partial(&["he", "llo", " wor"]), now);
+        assert_eq!(r.stable_text(), "hello wor");
+
+        // a two-pass final carries the tokens of the offline model,
+        // it replaces the stable text of the online partials
+        let r = stabilizer.update_at(&final_(&["Hello", ",", " world", "."]), now);
+        assert!(r.is_final());
+        assert_eq!(r.tokens(), ["Hello", ",", " world", "."]);
+        assert_eq!(r.stable_text(), "Hello, world.");
+        assert_eq!(r.unstable_tokens(), 4..4);
+    }
+}
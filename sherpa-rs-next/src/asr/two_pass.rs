@@ -0,0 +1,117 @@
+use crate::asr::{
+    offline::{AsrOfflineRecognizer, AsrOfflineResult},
+    online::{AsrOnlineRecognizer, AsrOnlineResult},
+};
+
+/// Streaming partials from an online model,
+/// each finished segment is decoded again by an offline model.
+/// Segments end at endpoints, so the online config needs `with_enable_endpoint(true)`,
+/// otherwise segments only end at `finish` or at the max segment length
+pub struct TwoPassRecognizer {
+    online: AsrOnlineRecognizer,
+    offline: AsrOfflineRecognizer,
+    // audio of the current segment
+    buffer: Vec<f32>,
+    sample_rate: u32,
+    // seconds
+    max_segment_length: f32,
+}
+
+pub struct TwoPassResult {
+    online: AsrOnlineResult,
+    offline: Option<AsrOfflineResult>,
+}
+
+impl TwoPassResult {
+    /// refined text for a final result, the online partial otherwise
+    pub fn text(&self) -> &str {
+        match &self.offline {
+            Some(offline) => offline.text(),
+            None => self.online.text(),
+        }
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.online.is_final()
+    }
+
+    pub fn segment_id(&self) -> i32 {
+        self.online.segment_id()
+    }
+
+    pub fn online(&self) -> &AsrOnlineResult {
+        &self.online
+    }
+
+    /// result of the second pass, only set for final results
+    pub fn offline(&self) -> Option<&AsrOfflineResult> {
+        self.offline.as_ref()
+    }
+}
+
+impl TwoPassRecognizer {
+    pub fn new(online: AsrOnlineRecognizer, offline: AsrOfflineRecognizer) -> Self {
+        Self {
+            online,
+            offline,
+            buffer: Vec::new(),
+            sample_rate: 16000,
+            max_segment_length: 60.0,
+        }
+    }
+
+    /// longest segment before it is closed without an endpoint, 60 seconds by default
+    pub fn with_max_segment_length(&mut self, seconds: f32) -> &mut Self {
+        self.max_segment_length = seconds;
+        self
+    }
+
+    /// `samples` of one call sequence must share the same sample rate.
+    /// A segment longer than the max segment length is closed as if input ended,
+    /// the result is final and the next samples start a new segment
+    pub fn transcribe(
+        &mut self,
+        sample_rate: u32,
+        samples: &[f32],
+    ) -> anyhow::Result<TwoPassResult> {
+        self.sample_rate = sample_rate;
+        self.buffer.extend_from_slice(samples);
+        let online = self.online.transcribe(sample_rate, samples)?;
+        if online.is_final() {
+            return self.second_pass(online);
+        }
+
+        let max_samples = (self.max_segment_length * sample_rate as f32) as usize;
+        if self.buffer.len() > max_samples {
+            // no endpoint yet, end the online segment with the buffered audio
+            // so that both passes decode the same samples
+            return self.finish();
+        }
+        Ok(TwoPassResult {
+            online,
+            offline: None,
+        })
+    }
+
+    /// Flushes the online recognizer and refines the last segment
+    pub fn finish(&mut self) -> anyhow::Result<TwoPassResult> {
+        let online = self.online.finish()?;
+        self.second_pass(online)
+    }
+
+    fn second_pass(&mut self, online: AsrOnlineResult) -> anyhow::Result<TwoPassResult> {
+        let samples = std::mem::take(&mut self.buffer);
+        // nothing was recognized, e.g. the segment is silence
+        if online.text().is_empty() || samples.is_empty() {
+            return Ok(TwoPassResult {
+                online,
+                offline: None,
+            });
+        }
+        let offline = self.offline.transcribe(self.sample_rate, &samples)?;
+        Ok(TwoPassResult {
+            online,
+            offline: Some(offline),
+        })
+    }
+}
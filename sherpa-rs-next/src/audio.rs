@@ -1,10 +1,18 @@
 pub fn read_audio_file(path: &str) -> anyhow::Result<(Vec<f32>, u32)> {
+    read_audio_file_with_sample_rate(path, 16000)
+}
+
+/// e.g. 8000 for narrowband telephony audio
+pub fn read_audio_file_with_sample_rate(
+    path: &str,
+    expected_sample_rate: u32,
+) -> anyhow::Result<(Vec<f32>, u32)> {
     let mut reader = hound::WavReader::open(path)?;
     let sample_rate = reader.spec().sample_rate;
 
-    // Check if the sample rate is 16000
-    if sample_rate != 16000 {
-        anyhow::bail!("The sample rate must be 16000.");
+    // Check if the sample rate is the expected one
+    if sample_rate != expected_sample_rate {
+        anyhow::bail!("The sample rate must be {}.", expected_sample_rate);
     }
 
     // Collect samples into a Vec<f32>
@@ -0,0 +1,57 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, tts::offline::TTSBaseConfig};
+
+#[derive(Debug, Default, FromBaseConfig)]
+pub struct KittenTTSConfig {
+    #[base_config(path = "src/tts/offline/mod.rs")]
+    base: TTSBaseConfig,
+
+    model: Option<CString>,
+    voices: Option<CString>,
+    tokens: Option<CString>,
+    data_dir: Option<CString>,
+}
+
+impl KittenTTSConfig {
+    pub fn with_model(&mut self, model: &str) -> &mut Self {
+        let model = as_c_string!(model);
+        self.base.config.model.kitten.model = model.as_ptr();
+        self.model = Some(model);
+        self
+    }
+
+    pub fn with_voices(&mut self, voices: &str) -> &mut Self {
+        let voices = as_c_string!(voices);
+        self.base.config.model.kitten.voices = voices.as_ptr();
+        self.voices = Some(voices);
+        self
+    }
+
+    pub fn with_tokens(&mut self, tokens: &str) -> &mut Self {
+        let tokens = as_c_string!(tokens);
+        self.base.config.model.kitten.tokens = tokens.as_ptr();
+        self.tokens = Some(tokens);
+        self
+    }
+
+    pub fn with_data_dir(&mut self, data_dir: &str) -> &mut Self {
+        let data_dir = as_c_string!(data_dir);
+        self.base.config.model.kitten.data_dir = data_dir.as_ptr();
+        self.data_dir = Some(data_dir);
+        self
+    }
+
+    pub fn with_length_scale(&mut self, length_scale: f32) -> &mut Self {
+        self.base.config.model.kitten.length_scale = length_scale;
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineTtsConfig> for KittenTTSConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineTtsConfig {
+        &self.base.config
+    }
+}
@@ -2,7 +2,10 @@ use std::ffi::CString;
 
 use sherpa_rs_next_macro::FromBaseConfig;
 
-use crate::{as_c_string, tts::offline::TTSBaseConfig};
+use crate::{
+    as_c_string,
+    tts::offline::{OfflineTTS, TTSBaseConfig},
+};
 
 #[derive(Debug, Default, FromBaseConfig)]
 pub struct KokoroTTSConfig {
@@ -86,3 +89,249 @@ impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineTtsConfig> for KokoroTTSConfig {
         &self.base.config
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KokoroLanguage {
+    AmericanEnglish,
+    BritishEnglish,
+    Spanish,
+    French,
+    Hindi,
+    Italian,
+    Japanese,
+    BrazilianPortuguese,
+    Mandarin,
+}
+
+impl KokoroLanguage {
+    /// the first letter of a voice name, e.g. 'z' for "zf_xiaobei"
+    fn from_prefix(prefix: char) -> Option<Self> {
+        match prefix {
+            'a' => Some(Self::AmericanEnglish),
+            'b' => Some(Self::BritishEnglish),
+            'e' => Some(Self::Spanish),
+            'f' => Some(Self::French),
+            'h' => Some(Self::Hindi),
+            'i' => Some(Self::Italian),
+            'j' => Some(Self::Japanese),
+            'p' => Some(Self::BrazilianPortuguese),
+            'z' => Some(Self::Mandarin),
+            _ => None,
+        }
+    }
+
+    /// language code as accepted by `KokoroTTSConfig::with_lang`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::AmericanEnglish => "en-us",
+            Self::BritishEnglish => "en-gb",
+            Self::Spanish => "es",
+            Self::French => "fr",
+            Self::Hindi => "hi",
+            Self::Italian => "it",
+            Self::Japanese => "ja",
+            Self::BrazilianPortuguese => "pt-br",
+            Self::Mandarin => "zh",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KokoroGender {
+    Female,
+    Male,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KokoroVoice {
+    name: &'static str,
+    sid: i32,
+    language: KokoroLanguage,
+    gender: KokoroGender,
+}
+
+impl KokoroVoice {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn sid(&self) -> i32 {
+        self.sid
+    }
+
+    pub fn language(&self) -> KokoroLanguage {
+        self.language
+    }
+
+    pub fn gender(&self) -> KokoroGender {
+        self.gender
+    }
+}
+
+/// Voices of a voices.bin release, in speaker id order.
+/// Only the v0.19 English and the v1.0 multi-language releases are covered,
+/// use `for_model` or `sid_for_model` to make sure the loaded model matches
+#[derive(Debug, Clone, Copy)]
+pub struct KokoroVoices(&'static [&'static str]);
+
+impl KokoroVoices {
+    /// kokoro-en-v0_19
+    pub const EN_V0_19: Self = Self(&[
+        "af",
+        "af_bella",
+        "af_nicole",
+        "af_sarah",
+        "af_sky",
+        "am_adam",
+        "am_michael",
+        "bf_emma",
+        "bf_isabella",
+        "bm_george",
+        "bm_lewis",
+    ]);
+
+    /// kokoro-multi-lang-v1_0
+    pub const MULTI_LANG_V1_0: Self = Self(&[
+        "af_alloy",
+        "af_aoede",
+        "af_bella",
+        "af_heart",
+        "af_jessica",
+        "af_kore",
+        "af_nicole",
+        "af_nova",
+        "af_river",
+        "af_sarah",
+        "af_sky",
+        "am_adam",
+        "am_echo",
+        "am_eric",
+        "am_fenrir",
+        "am_liam",
+        "am_michael",
+        "am_onyx",
+        "am_puck",
+        "am_santa",
+        "bf_alice",
+        "bf_emma",
+        "bf_isabella",
+        "bf_lily",
+        "bm_daniel",
+        "bm_fable",
+        "bm_george",
+        "bm_lewis",
+        "ef_dora",
+        "em_alex",
+        "ff_siwis",
+        "hf_alpha",
+        "hf_beta",
+        "hm_omega",
+        "hm_psi",
+        "if_sara",
+        "im_nicola",
+        "jf_alpha",
+        "jf_gongitsune",
+        "jf_nezumi",
+        "jf_tebukuro",
+        "jm_kumo",
+        "pf_dora",
+        "pm_alex",
+        "pm_santa",
+        "zf_xiaobei",
+        "zf_xiaoni",
+        "zf_xiaoxiao",
+        "zf_xiaoyi",
+        "zm_yunjian",
+        "zm_yunxi",
+        "zm_yunxia",
+        "zm_yunyang",
+    ]);
+
+    const RELEASES: [Self; 2] = [Self::EN_V0_19, Self::MULTI_LANG_V1_0];
+
+    /// the known release with the same number of speakers as the loaded model
+    pub fn for_model(tts: &OfflineTTS) -> anyhow::Result<Self> {
+        let num_speakers = tts.num_speakers();
+        Self::RELEASES
+            .into_iter()
+            .find(|voices| voices.num_speakers() == num_speakers)
+            .ok_or_else(|| anyhow::anyhow!("No known kokoro release has {} speakers", num_speakers))
+    }
+
+    pub fn num_speakers(&self) -> i32 {
+        self.0.len() as i32
+    }
+
+    pub fn voices(&self) -> impl Iterator<Item = KokoroVoice> + '_ {
+        (0..self.0.len()).filter_map(|sid| self.voice(sid as i32))
+    }
+
+    pub fn voice(&self, sid: i32) -> Option<KokoroVoice> {
+        let name = *self.0.get(usize::try_from(sid).ok()?)?;
+        let mut prefix = name.chars();
+        let language = KokoroLanguage::from_prefix(prefix.next()?)?;
+        let gender = match prefix.next()? {
+            'f' => KokoroGender::Female,
+            'm' => KokoroGender::Male,
+            _ => return None,
+        };
+        Some(KokoroVoice {
+            name,
+            sid,
+            language,
+            gender,
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<KokoroVoice> {
+        let sid = self.0.iter().position(|voice| *voice == name)?;
+        self.voice(sid as i32)
+    }
+
+    pub fn sid(&self, name: &str) -> anyhow::Result<i32> {
+        self.get(name)
+            .map(|voice| voice.sid)
+            .ok_or_else(|| anyhow::anyhow!("Unknown kokoro voice: {}", name))
+    }
+
+    /// same as `sid`, fails if the loaded model is not this release
+    pub fn sid_for_model(&self, tts: &OfflineTTS, name: &str) -> anyhow::Result<i32> {
+        if tts.num_speakers() != self.num_speakers() {
+            return Err(anyhow::anyhow!(
+                "The model has {} speakers, the kokoro catalog has {}",
+                tts.num_speakers(),
+                self.num_speakers()
+            ));
+        }
+        self.sid(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kokoro_voices() {
+        let voices = KokoroVoices::MULTI_LANG_V1_0;
+        assert_eq!(voices.num_speakers(), 53);
+        assert_eq!(voices.voices().count(), 53);
+
+        let heart = voices.get("af_heart").unwrap();
+        assert_eq!(heart.sid(), 3);
+        assert_eq!(heart.language(), KokoroLanguage::AmericanEnglish);
+        assert_eq!(heart.gender(), KokoroGender::Female);
+
+        let xiaobei = voices.get("zf_xiaobei").unwrap();
+        assert_eq!(xiaobei.sid(), 45);
+        assert_eq!(xiaobei.language().code(), "zh");
+
+        assert_eq!(voices.sid("bm_george").unwrap(), 26);
+        assert!(voices.sid("xx_unknown").is_err());
+        assert!(voices.voice(53).is_none());
+        assert!(voices.voice(-1).is_none());
+
+        assert_eq!(KokoroVoices::EN_V0_19.sid("af").unwrap(), 0);
+        assert_eq!(KokoroVoices::EN_V0_19.num_speakers(), 11);
+    }
+}
@@ -0,0 +1,117 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, tts::offline::TTSBaseConfig};
+
+#[derive(Debug, Default, FromBaseConfig)]
+pub struct MatchaTTSConfig {
+    #[base_config(path = "src/tts/offline/mod.rs")]
+    base: TTSBaseConfig,
+
+    acoustic_model: Option<CString>,
+    vocoder: Option<CString>,
+    lexicon: Option<CString>,
+    tokens: Option<CString>,
+    data_dir: Option<CString>,
+    dict_dir: Option<CString>,
+}
+
+impl MatchaTTSConfig {
+    pub fn with_acoustic_model(&mut self, acoustic_model: &str) -> &mut Self {
+        let acoustic_model = as_c_string!(acoustic_model);
+        self.base.config.model.matcha.acoustic_model = acoustic_model.as_ptr();
+        self.acoustic_model = Some(acoustic_model);
+        self
+    }
+
+    /// vocoder model, e.g. vocos-22khz-univ.onnx or hifigan_v2.onnx
+    pub fn with_vocoder(&mut self, vocoder: &str) -> &mut Self {
+        let vocoder = as_c_string!(vocoder);
+        self.base.config.model.matcha.vocoder = vocoder.as_ptr();
+        self.vocoder = Some(vocoder);
+        self
+    }
+
+    pub fn with_lexicon(&mut self, lexicon: &str) -> &mut Self {
+        let lexicon = as_c_string!(lexicon);
+        self.base.config.model.matcha.lexicon = lexicon.as_ptr();
+        self.lexicon = Some(lexicon);
+        self
+    }
+
+    pub fn with_tokens(&mut self, tokens: &str) -> &mut Self {
+        let tokens = as_c_string!(tokens);
+        self.base.config.model.matcha.tokens = tokens.as_ptr();
+        self.tokens = Some(tokens);
+        self
+    }
+
+    pub fn with_data_dir(&mut self, data_dir: &str) -> &mut Self {
+        let data_dir = as_c_string!(data_dir);
+        self.base.config.model.matcha.data_dir = data_dir.as_ptr();
+        self.data_dir = Some(data_dir);
+        self
+    }
+
+    pub fn with_dict_dir(&mut self, dict_dir: &str) -> &mut Self {
+        let dict_dir = as_c_string!(dict_dir);
+        self.base.config.model.matcha.dict_dir = dict_dir.as_ptr();
+        self.dict_dir = Some(dict_dir);
+        self
+    }
+
+    pub fn with_noise_scale(&mut self, noise_scale: f32) -> &mut Self {
+        self.base.config.model.matcha.noise_scale = noise_scale;
+        self
+    }
+
+    pub fn with_length_scale(&mut self, length_scale: f32) -> &mut Self {
+        self.base.config.model.matcha.length_scale = length_scale;
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineTtsConfig> for MatchaTTSConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineTtsConfig {
+        &self.base.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_matcha_tts_config() {
+        let mut config = MatchaTTSConfig::default();
+        config
+            .with_acoustic_model("model-steps-3.onnx")
+            .with_vocoder("vocos-22khz-univ.onnx")
+            .with_lexicon("lexicon.txt")
+            .with_tokens("tokens.txt")
+            .with_dict_dir("dict")
+            .with_noise_scale(0.5)
+            .with_length_scale(1.1)
+            .with_rule_fsts("phone.fst,date.fst");
+        let config: &sherpa_rs_sys::SherpaOnnxOfflineTtsConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model.matcha.acoustic_model),
+            "model-steps-3.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model.matcha.vocoder),
+            "vocos-22khz-univ.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model.matcha.lexicon),
+            "lexicon.txt"
+        );
+        assert_eq!(const_ptr_to_string!(config.model.matcha.dict_dir), "dict");
+        assert_eq!(config.model.matcha.noise_scale, 0.5);
+        assert_eq!(config.model.matcha.length_scale, 1.1);
+        assert_eq!(const_ptr_to_string!(config.rule_fsts), "phone.fst,date.fst");
+    }
+}
@@ -1,8 +1,13 @@
-use std::ffi::CString;
+use std::ffi::{CString, c_void};
 
 use crate::as_c_string;
 
+pub mod kitten;
 pub mod kokoro;
+pub mod matcha;
+pub mod ssml;
+pub mod vits;
+pub mod zipvoice;
 
 pub type TTSConfig = Box<dyn AsRef<sherpa_rs_sys::SherpaOnnxOfflineTtsConfig>>;
 
@@ -40,6 +45,70 @@ impl TTSBaseConfig {
     }
 }
 
+/// Per-request options of `OfflineTTS::generate_with_config` and `generate_with_callback`
+#[derive(Debug)]
+pub struct GenerationConfig {
+    config: sherpa_rs_sys::SherpaOnnxGenerationConfig,
+
+    reference_audio: Vec<f32>,
+    reference_text: Option<CString>,
+}
+
+impl Default for GenerationConfig {
+    fn default() -> Self {
+        let mut config = Self {
+            config: Default::default(),
+            reference_audio: Vec::new(),
+            reference_text: None,
+        };
+        config
+            .with_speed(1.0)
+            .with_silence_scale(0.2)
+            .with_num_steps(4);
+        config
+    }
+}
+
+impl GenerationConfig {
+    pub fn with_sid(&mut self, sid: i32) -> &mut Self {
+        self.config.sid = sid;
+        self
+    }
+
+    pub fn with_speed(&mut self, speed: f32) -> &mut Self {
+        self.config.speed = speed;
+        self
+    }
+
+    pub fn with_silence_scale(&mut self, silence_scale: f32) -> &mut Self {
+        self.config.silence_scale = silence_scale;
+        self
+    }
+
+    /// prompt voice for zero-shot models such as ZipVoice
+    pub fn with_reference_audio(&mut self, samples: &[f32], sample_rate: i32) -> &mut Self {
+        self.reference_audio = samples.to_vec();
+        self.config.reference_audio = self.reference_audio.as_ptr();
+        self.config.reference_audio_len = self.reference_audio.len() as i32;
+        self.config.reference_sample_rate = sample_rate;
+        self
+    }
+
+    /// transcript of the reference audio
+    pub fn with_reference_text(&mut self, reference_text: &str) -> &mut Self {
+        let reference_text = as_c_string!(reference_text);
+        self.config.reference_text = reference_text.as_ptr();
+        self.reference_text = Some(reference_text);
+        self
+    }
+
+    /// flow-matching steps of ZipVoice
+    pub fn with_num_steps(&mut self, num_steps: i32) -> &mut Self {
+        self.config.num_steps = num_steps;
+        self
+    }
+}
+
 pub struct TTSAudio {
     samples: Vec<f32>,
     sample_rate: i32,
@@ -77,41 +146,169 @@ impl OfflineTTS {
         Ok(Self(tts))
     }
 
+    /// sample rate of the generated audio
+    pub fn sample_rate(&self) -> i32 {
+        unsafe { sherpa_rs_sys::SherpaOnnxOfflineTtsSampleRate(self.0) }
+    }
+
+    pub fn num_speakers(&self) -> i32 {
+        unsafe { sherpa_rs_sys::SherpaOnnxOfflineTtsNumSpeakers(self.0) }
+    }
+
+    fn check_sid(&self, sid: i32) -> anyhow::Result<()> {
+        let num_speakers = self.num_speakers();
+        // single speaker and zero-shot models report no speakers
+        if num_speakers > 0 && !(0..num_speakers).contains(&sid) {
+            return Err(anyhow::anyhow!(
+                "Invalid sid {}, the model has {} speakers",
+                sid,
+                num_speakers
+            ));
+        }
+        Ok(())
+    }
+
     pub fn generate(&self, text: &str, sid: i32, speed: f32) -> anyhow::Result<TTSAudio> {
+        self.check_sid(sid)?;
         let text = as_c_string!(text);
         unsafe {
             let audio_ptr =
                 sherpa_rs_sys::SherpaOnnxOfflineTtsGenerate(self.0, text.as_ptr(), sid, speed);
+            generated_audio(audio_ptr)
+        }
+    }
 
-            if audio_ptr.is_null() {
-                sherpa_rs_sys::SherpaOnnxDestroyOfflineTtsGeneratedAudio(audio_ptr);
-                return Err(anyhow::anyhow!("Failed to generate audio"));
-            }
+    pub fn generate_with_config(
+        &self,
+        text: &str,
+        config: &GenerationConfig,
+    ) -> anyhow::Result<TTSAudio> {
+        unsafe { self.generate_raw(text, config, None, std::ptr::null_mut()) }
+    }
 
-            let audio = audio_ptr.read();
+    /// Calls `callback` with the samples of each sentence as soon as it is synthesized
+    /// and the progress in `[0, 1]`, generation stops when it returns `false`.
+    /// Returns the audio generated so far
+    pub fn generate_with_callback<F>(
+        &self,
+        text: &str,
+        config: &GenerationConfig,
+        callback: F,
+    ) -> anyhow::Result<TTSAudio>
+    where
+        F: FnMut(&[f32], f32) -> bool,
+    {
+        let mut state = CallbackState {
+            callback,
+            panic: None,
+        };
+        let audio = unsafe {
+            self.generate_raw(
+                text,
+                config,
+                Some(progress_callback::<F>),
+                &mut state as *mut CallbackState<F> as *mut c_void,
+            )
+        };
+        // the generated audio is destroyed, the panic can continue on the Rust side
+        if let Some(panic) = state.panic {
+            std::panic::resume_unwind(panic);
+        }
+        audio
+    }
 
-            if audio.n.is_negative() {
-                sherpa_rs_sys::SherpaOnnxDestroyOfflineTtsGeneratedAudio(audio_ptr);
-                return Err(anyhow::anyhow!("no samples found"));
-            }
+    /// `arg` must be what `callback` expects for the whole call
+    unsafe fn generate_raw(
+        &self,
+        text: &str,
+        config: &GenerationConfig,
+        callback: sherpa_rs_sys::SherpaOnnxGeneratedAudioProgressCallbackWithArg,
+        arg: *mut c_void,
+    ) -> anyhow::Result<TTSAudio> {
+        self.check_sid(config.config.sid)?;
+        let text = as_c_string!(text);
+        unsafe {
+            let audio_ptr = sherpa_rs_sys::SherpaOnnxOfflineTtsGenerateWithConfig(
+                self.0,
+                text.as_ptr(),
+                &config.config,
+                callback,
+                arg,
+            );
+            generated_audio(audio_ptr)
+        }
+    }
+}
 
-            if audio.samples.is_null() {
-                sherpa_rs_sys::SherpaOnnxDestroyOfflineTtsGeneratedAudio(audio_ptr);
-                return Err(anyhow::anyhow!("no samples found"));
-            }
+struct CallbackState<F> {
+    callback: F,
+    panic: Option<Box<dyn std::any::Any + Send>>,
+}
 
-            let samples = std::slice::from_raw_parts(audio.samples, audio.n as usize);
-            let sample_rate = audio.sample_rate;
-            let duration = (samples.len() as i32) / sample_rate;
-            let res = TTSAudio {
-                samples: samples.to_vec(),
-                sample_rate,
-                duration,
-            };
+/// Unwinding out of an `extern "C"` function aborts the process,
+/// so a panic of the callback stops generation and is resumed by the caller
+unsafe extern "C" fn progress_callback<F>(
+    samples: *const f32,
+    n: i32,
+    progress: f32,
+    arg: *mut c_void,
+) -> i32
+where
+    F: FnMut(&[f32], f32) -> bool,
+{
+    let state = unsafe { &mut *(arg as *mut CallbackState<F>) };
+    if state.panic.is_some() {
+        return 0;
+    }
+    let samples = if samples.is_null() || n <= 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(samples, n as usize) }
+    };
+    let callback = &mut state.callback;
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| callback(samples, progress))) {
+        Ok(true) => 1,
+        Ok(false) => 0,
+        Err(panic) => {
+            state.panic = Some(panic);
+            0
+        }
+    }
+}
 
+/// Copies the samples out and destroys the generated audio
+unsafe fn generated_audio(
+    audio_ptr: *const sherpa_rs_sys::SherpaOnnxGeneratedAudio,
+) -> anyhow::Result<TTSAudio> {
+    unsafe {
+        if audio_ptr.is_null() {
             sherpa_rs_sys::SherpaOnnxDestroyOfflineTtsGeneratedAudio(audio_ptr);
-            Ok(res)
+            return Err(anyhow::anyhow!("Failed to generate audio"));
         }
+
+        let audio = audio_ptr.read();
+
+        if audio.n.is_negative() {
+            sherpa_rs_sys::SherpaOnnxDestroyOfflineTtsGeneratedAudio(audio_ptr);
+            return Err(anyhow::anyhow!("no samples found"));
+        }
+
+        if audio.samples.is_null() {
+            sherpa_rs_sys::SherpaOnnxDestroyOfflineTtsGeneratedAudio(audio_ptr);
+            return Err(anyhow::anyhow!("no samples found"));
+        }
+
+        let samples = std::slice::from_raw_parts(audio.samples, audio.n as usize);
+        let sample_rate = audio.sample_rate;
+        let duration = (samples.len() as i32) / sample_rate;
+        let res = TTSAudio {
+            samples: samples.to_vec(),
+            sample_rate,
+            duration,
+        };
+
+        sherpa_rs_sys::SherpaOnnxDestroyOfflineTtsGeneratedAudio(audio_ptr);
+        Ok(res)
     }
 }
 
@@ -122,3 +319,46 @@ impl Drop for OfflineTTS {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call<F: FnMut(&[f32], f32) -> bool>(state: &mut CallbackState<F>, samples: &[f32]) -> i32 {
+        unsafe {
+            progress_callback::<F>(
+                samples.as_ptr(),
+                samples.len() as i32,
+                0.5,
+                state as *mut CallbackState<F> as *mut c_void,
+            )
+        }
+    }
+
+    #[test]
+    fn test_progress_callback() {
+        let mut received = Vec::new();
+        let mut state = CallbackState {
+            callback: |samples: &[f32], progress: f32| {
+                received.push((samples.len(), progress));
+                samples.len() < 2
+            },
+            panic: None,
+        };
+        assert_eq!(call(&mut state, &[0.0]), 1);
+        assert_eq!(call(&mut state, &[0.0, 0.0]), 0);
+        drop(state);
+        assert_eq!(received, vec![(1, 0.5), (2, 0.5)]);
+    }
+
+    #[test]
+    fn test_progress_callback_panic() {
+        let mut state = CallbackState {
+            callback: |_: &[f32], _: f32| -> bool { panic!("callback failed") },
+            panic: None,
+        };
+        assert_eq!(call(&mut state, &[0.0]), 0);
+        let panic = state.panic.take().unwrap();
+        assert_eq!(panic.downcast_ref::<&str>(), Some(&"callback failed"));
+    }
+}
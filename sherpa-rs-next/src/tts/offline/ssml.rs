@@ -0,0 +1,442 @@
+use std::collections::HashMap;
+
+use crate::tts::offline::{OfflineTTS, TTSAudio, kokoro::KokoroVoices};
+
+/// A piece of SSML input, texts with the same settings are merged
+#[derive(Debug, Clone, PartialEq)]
+pub enum SsmlSegment {
+    Text {
+        text: String,
+        voice: Option<String>,
+        lang: Option<String>,
+        rate: f32,
+    },
+    /// silence in seconds
+    Break(f32),
+}
+
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    tag: String,
+    voice: Option<String>,
+    lang: Option<String>,
+    rate: f32,
+    say_as: Option<String>,
+    // inside `<sub>`, the text is replaced by the alias
+    skip_text: bool,
+}
+
+/// Parses the SSML subset `<speak>`, `<break>`, `<prosody rate>`, `<voice name>`,
+/// `<lang>`, `<say-as>` and `<sub>`, other tags are ignored but must be balanced
+pub fn parse(ssml: &str) -> anyhow::Result<Vec<SsmlSegment>> {
+    let mut segments = Vec::new();
+    let mut stack = vec![Scope {
+        rate: 1.0,
+        ..Default::default()
+    }];
+
+    let mut rest = ssml;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut segments, stack.last().unwrap(), rest);
+            break;
+        };
+        push_text(&mut segments, stack.last().unwrap(), &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment
+                .find("-->")
+                .ok_or_else(|| anyhow::anyhow!("Unclosed comment"))?;
+            rest = &comment[end + 3..];
+            continue;
+        }
+
+        let end = rest
+            .find('>')
+            .ok_or_else(|| anyhow::anyhow!("Unclosed tag: {}", rest))?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        // xml declaration and doctype
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            if name == "break" {
+                continue;
+            }
+            // the root scope has no tag
+            if stack.len() == 1 || stack.last().unwrap().tag != name {
+                return Err(anyhow::anyhow!("Unexpected closing tag: </{}>", name));
+            }
+            stack.pop();
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let (name, attrs) = match tag.find(char::is_whitespace) {
+            Some(i) => (&tag[..i], parse_attrs(&tag[i..])?),
+            None => (tag, HashMap::new()),
+        };
+
+        let mut scope = stack.last().unwrap().clone();
+        scope.tag = name.to_string();
+        match name {
+            "break" => {
+                segments.push(SsmlSegment::Break(parse_break(&attrs)?));
+                continue;
+            }
+            "speak" | "lang" => {
+                if let Some(lang) = attrs.get("xml:lang").or_else(|| attrs.get("lang")) {
+                    scope.lang = Some(lang.clone());
+                }
+            }
+            "voice" => {
+                if let Some(voice) = attrs.get("name") {
+                    scope.voice = Some(voice.clone());
+                }
+                if let Some(lang) = attrs.get("xml:lang") {
+                    scope.lang = Some(lang.clone());
+                }
+            }
+            "prosody" => {
+                if let Some(rate) = attrs.get("rate") {
+                    scope.rate *= parse_rate(rate)?;
+                }
+            }
+            "say-as" => {
+                scope.say_as = attrs.get("interpret-as").cloned();
+            }
+            "sub" => {
+                let alias = attrs
+                    .get("alias")
+                    .ok_or_else(|| anyhow::anyhow!("<sub> without alias"))?;
+                push_text(&mut segments, &scope, alias);
+                scope.skip_text = true;
+            }
+            _ => {}
+        }
+        if !self_closing {
+            stack.push(scope);
+        }
+    }
+
+    if stack.len() > 1 {
+        return Err(anyhow::anyhow!(
+            "Unclosed tag: <{}>",
+            stack.last().unwrap().tag
+        ));
+    }
+
+    segments.retain_mut(|segment| match segment {
+        SsmlSegment::Text { text, .. } => {
+            *text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            !text.is_empty()
+        }
+        SsmlSegment::Break(_) => true,
+    });
+    Ok(segments)
+}
+
+fn push_text(segments: &mut Vec<SsmlSegment>, scope: &Scope, text: &str) {
+    if scope.skip_text || text.is_empty() {
+        return;
+    }
+    let mut text = decode_entities(text);
+    if let Some("characters" | "spell-out" | "verbatim" | "digits" | "telephone") =
+        scope.say_as.as_deref()
+    {
+        text = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+
+    if let Some(SsmlSegment::Text {
+        text: last,
+        voice,
+        lang,
+        rate,
+    }) = segments.last_mut()
+    {
+        if *voice == scope.voice && *lang == scope.lang && *rate == scope.rate {
+            last.push_str(&text);
+            return;
+        }
+    }
+    segments.push(SsmlSegment::Text {
+        text,
+        voice: scope.voice.clone(),
+        lang: scope.lang.clone(),
+        rate: scope.rate,
+    });
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn parse_attrs(attrs: &str) -> anyhow::Result<HashMap<String, String>> {
+    let mut map = HashMap::new();
+    let mut rest = attrs.trim();
+    while !rest.is_empty() {
+        let eq = rest
+            .find('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid attribute: {}", rest))?;
+        let name = rest[..eq].trim();
+        let value = rest[eq + 1..].trim_start();
+        let quote = value
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or_else(|| anyhow::anyhow!("Unquoted attribute value: {}", name))?;
+        let end = value[1..]
+            .find(quote)
+            .ok_or_else(|| anyhow::anyhow!("Unclosed attribute value: {}", name))?;
+        map.insert(name.to_string(), decode_entities(&value[1..end + 1]));
+        rest = value[end + 2..].trim_start();
+    }
+    Ok(map)
+}
+
+/// `time` such as "500ms" or "1.5s", or `strength`
+fn parse_break(attrs: &HashMap<String, String>) -> anyhow::Result<f32> {
+    if let Some(time) = attrs.get("time") {
+        let time = time.trim();
+        let seconds = if let Some(ms) = time.strip_suffix("ms") {
+            ms.trim().parse::<f32>().map(|ms| ms / 1000.0)
+        } else {
+            time.trim_end_matches('s').trim().parse::<f32>()
+        };
+        return seconds.map_err(|_| anyhow::anyhow!("Invalid break time: {}", time));
+    }
+
+    match attrs.get("strength").map(String::as_str) {
+        Some("none") => Ok(0.0),
+        Some("x-weak") => Ok(0.1),
+        Some("weak") => Ok(0.25),
+        Some("medium") | None => Ok(0.5),
+        Some("strong") => Ok(0.75),
+        Some("x-strong") => Ok(1.0),
+        Some(strength) => Err(anyhow::anyhow!("Invalid break strength: {}", strength)),
+    }
+}
+
+/// keywords, percentages such as "120%" or "-10%", or a plain factor
+fn parse_rate(rate: &str) -> anyhow::Result<f32> {
+    let rate = rate.trim();
+    let factor = match rate {
+        "x-slow" => Some(0.5),
+        "slow" => Some(0.75),
+        "medium" | "default" => Some(1.0),
+        "fast" => Some(1.25),
+        "x-fast" => Some(1.5),
+        _ => match rate.strip_suffix('%') {
+            Some(percent) if percent.starts_with(['+', '-']) => {
+                percent.parse::<f32>().ok().map(|p| 1.0 + p / 100.0)
+            }
+            Some(percent) => percent.parse::<f32>().ok().map(|p| p / 100.0),
+            None => rate.parse::<f32>().ok(),
+        },
+    };
+    factor
+        .filter(|factor| *factor > 0.0)
+        .ok_or_else(|| anyhow::anyhow!("Invalid prosody rate: {}", rate))
+}
+
+/// Synthesizes SSML with one `OfflineTTS::generate` call per text segment
+pub struct SsmlSynthesizer<'a> {
+    tts: &'a OfflineTTS,
+    sid: i32,
+    speed: f32,
+    voices: HashMap<String, i32>,
+    langs: HashMap<String, i32>,
+}
+
+impl<'a> SsmlSynthesizer<'a> {
+    pub fn new(tts: &'a OfflineTTS) -> Self {
+        Self {
+            tts,
+            sid: 0,
+            speed: 1.0,
+            voices: HashMap::new(),
+            langs: HashMap::new(),
+        }
+    }
+
+    /// Resolves `<voice name>` with the kokoro release of the loaded model,
+    /// e.g. `<voice name="zf_xiaobei">`
+    pub fn for_kokoro(tts: &'a OfflineTTS) -> anyhow::Result<Self> {
+        let voices = KokoroVoices::for_model(tts)?;
+        let mut synthesizer = Self::new(tts);
+        synthesizer.with_kokoro_voices(voices);
+        Ok(synthesizer)
+    }
+
+    /// speaker used when the text has no voice and no lang mapped by `with_lang`
+    pub fn with_sid(&mut self, sid: i32) -> &mut Self {
+        self.sid = sid;
+        self
+    }
+
+    /// base speed, multiplied by `<prosody rate>`
+    pub fn with_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed;
+        self
+    }
+
+    /// maps `<voice name>` to a speaker id, numeric names are used as sid directly
+    pub fn with_voice(&mut self, name: &str, sid: i32) -> &mut Self {
+        self.voices.insert(name.to_string(), sid);
+        self
+    }
+
+    /// maps every voice name of a kokoro release to its speaker id
+    pub fn with_kokoro_voices(&mut self, voices: KokoroVoices) -> &mut Self {
+        for voice in voices.voices() {
+            self.with_voice(voice.name(), voice.sid());
+        }
+        self
+    }
+
+    /// Speaker used for `<lang xml:lang>` when no voice is given.
+    /// Only mapped langs select a speaker, other langs use the default sid,
+    /// since most documents carry a lang on `<speak>` anyway
+    pub fn with_lang(&mut self, lang: &str, sid: i32) -> &mut Self {
+        self.langs.insert(lang.to_string(), sid);
+        self
+    }
+
+    pub fn generate(&self, ssml: &str) -> anyhow::Result<TTSAudio> {
+        let mut samples = Vec::new();
+        let mut sample_rate = 0;
+        // silence is inserted once the sample rate is known
+        let mut silence = 0.0;
+
+        for segment in parse(ssml)? {
+            match segment {
+                SsmlSegment::Break(seconds) => silence += seconds,
+                SsmlSegment::Text {
+                    text,
+                    voice,
+                    lang,
+                    rate,
+                } => {
+                    let sid = self.sid(voice.as_deref(), lang.as_deref())?;
+                    let audio = self.tts.generate(&text, sid, self.speed * rate)?;
+                    sample_rate = audio.sample_rate;
+                    push_silence(&mut samples, silence, sample_rate);
+                    silence = 0.0;
+                    samples.extend_from_slice(audio.samples());
+                }
+            }
+        }
+
+        if sample_rate == 0 {
+            return Err(anyhow::anyhow!("No text to synthesize"));
+        }
+        push_silence(&mut samples, silence, sample_rate);
+        let duration = (samples.len() as i32) / sample_rate;
+        Ok(TTSAudio {
+            samples,
+            sample_rate,
+            duration,
+        })
+    }
+
+    /// an unknown voice fails, an unmapped lang falls back to the default sid
+    fn sid(&self, voice: Option<&str>, lang: Option<&str>) -> anyhow::Result<i32> {
+        if let Some(voice) = voice {
+            return self
+                .voices
+                .get(voice)
+                .copied()
+                .or_else(|| voice.parse().ok())
+                .ok_or_else(|| anyhow::anyhow!("Unknown voice: {}", voice));
+        }
+        Ok(lang
+            .and_then(|lang| self.langs.get(lang))
+            .copied()
+            .unwrap_or(self.sid))
+    }
+}
+
+fn push_silence(samples: &mut Vec<f32>, seconds: f32, sample_rate: i32) {
+    let len = (seconds * sample_rate as f32) as usize;
+    samples.resize(samples.len() + len, 0.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str, voice: Option<&str>, lang: Option<&str>, rate: f32) -> SsmlSegment {
+        SsmlSegment::Text {
+            text: text.to_string(),
+            voice: voice.map(String::from),
+            lang: lang.map(String::from),
+            rate,
+        }
+    }
+
+    #[test]
+    fn test_parse_ssml() {
+        let segments = parse(
+            r#"<?xml version="1.0"?>
+            <speak xml:lang="en-US">
+                Hello <break time="300ms"/> world.
+                <prosody rate="slow">Take your time.</prosody>
+                <voice name="zf_xiaobei"><lang xml:lang="zh-CN">你好</lang></voice>
+                <break strength="strong"/>
+                Call <say-as interpret-as="digits">911</say-as>,
+                <sub alias="World Wide Web">WWW</sub> &amp; more.
+            </speak>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                text("Hello", None, Some("en-US"), 1.0),
+                SsmlSegment::Break(0.3),
+                text("world.", None, Some("en-US"), 1.0),
+                text("Take your time.", None, Some("en-US"), 0.75),
+                text("你好", Some("zf_xiaobei"), Some("zh-CN"), 1.0),
+                SsmlSegment::Break(0.75),
+                text(
+                    "Call 9 1 1, World Wide Web & more.",
+                    None,
+                    Some("en-US"),
+                    1.0
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rate() {
+        assert_eq!(parse_rate("120%").unwrap(), 1.2);
+        assert_eq!(parse_rate("+50%").unwrap(), 1.5);
+        assert_eq!(parse_rate("-50%").unwrap(), 0.5);
+        assert_eq!(parse_rate("x-fast").unwrap(), 1.5);
+        assert!(parse_rate("-100%").is_err());
+        assert!(parse_rate("quick").is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_ssml() {
+        assert!(parse("<speak>hello").is_err());
+        assert!(parse("<speak>hello</voice></speak>").is_err());
+        assert!(parse("<speak><break time=\"soon\"/></speak>").is_err());
+        assert!(parse("<speak><sub>WWW</sub></speak>").is_err());
+    }
+}
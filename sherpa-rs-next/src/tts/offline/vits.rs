@@ -0,0 +1,111 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, tts::offline::TTSBaseConfig};
+
+/// VITS models, including Piper and MeloTTS voices
+#[derive(Debug, Default, FromBaseConfig)]
+pub struct VitsTTSConfig {
+    #[base_config(path = "src/tts/offline/mod.rs")]
+    base: TTSBaseConfig,
+
+    model: Option<CString>,
+    lexicon: Option<CString>,
+    tokens: Option<CString>,
+    data_dir: Option<CString>,
+    dict_dir: Option<CString>,
+}
+
+impl VitsTTSConfig {
+    pub fn with_model(&mut self, model: &str) -> &mut Self {
+        let model = as_c_string!(model);
+        self.base.config.model.vits.model = model.as_ptr();
+        self.model = Some(model);
+        self
+    }
+
+    pub fn with_lexicon(&mut self, lexicon: &str) -> &mut Self {
+        let lexicon = as_c_string!(lexicon);
+        self.base.config.model.vits.lexicon = lexicon.as_ptr();
+        self.lexicon = Some(lexicon);
+        self
+    }
+
+    pub fn with_tokens(&mut self, tokens: &str) -> &mut Self {
+        let tokens = as_c_string!(tokens);
+        self.base.config.model.vits.tokens = tokens.as_ptr();
+        self.tokens = Some(tokens);
+        self
+    }
+
+    /// espeak-ng-data directory, required by Piper voices
+    pub fn with_data_dir(&mut self, data_dir: &str) -> &mut Self {
+        let data_dir = as_c_string!(data_dir);
+        self.base.config.model.vits.data_dir = data_dir.as_ptr();
+        self.data_dir = Some(data_dir);
+        self
+    }
+
+    pub fn with_dict_dir(&mut self, dict_dir: &str) -> &mut Self {
+        let dict_dir = as_c_string!(dict_dir);
+        self.base.config.model.vits.dict_dir = dict_dir.as_ptr();
+        self.dict_dir = Some(dict_dir);
+        self
+    }
+
+    pub fn with_noise_scale(&mut self, noise_scale: f32) -> &mut Self {
+        self.base.config.model.vits.noise_scale = noise_scale;
+        self
+    }
+
+    pub fn with_noise_scale_w(&mut self, noise_scale_w: f32) -> &mut Self {
+        self.base.config.model.vits.noise_scale_w = noise_scale_w;
+        self
+    }
+
+    pub fn with_length_scale(&mut self, length_scale: f32) -> &mut Self {
+        self.base.config.model.vits.length_scale = length_scale;
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineTtsConfig> for VitsTTSConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineTtsConfig {
+        &self.base.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_vits_tts_config() {
+        let mut config = VitsTTSConfig::default();
+        config
+            .with_model("en_US-amy-low.onnx")
+            .with_tokens("tokens.txt")
+            .with_data_dir("espeak-ng-data")
+            .with_noise_scale(0.667)
+            .with_noise_scale_w(0.8)
+            .with_length_scale(1.2)
+            .with_max_num_sentences(2);
+        let config: &sherpa_rs_sys::SherpaOnnxOfflineTtsConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model.vits.model),
+            "en_US-amy-low.onnx"
+        );
+        assert_eq!(const_ptr_to_string!(config.model.vits.tokens), "tokens.txt");
+        assert_eq!(
+            const_ptr_to_string!(config.model.vits.data_dir),
+            "espeak-ng-data"
+        );
+        assert_eq!(config.model.vits.noise_scale, 0.667);
+        assert_eq!(config.model.vits.noise_scale_w, 0.8);
+        assert_eq!(config.model.vits.length_scale, 1.2);
+        assert_eq!(config.max_num_sentences, 2);
+    }
+}
@@ -0,0 +1,125 @@
+use std::ffi::CString;
+
+use sherpa_rs_next_macro::FromBaseConfig;
+
+use crate::{as_c_string, tts::offline::TTSBaseConfig};
+
+/// Zero-shot voice cloning, the prompt voice and the number of flow-matching steps
+/// are passed per request with `GenerationConfig`
+#[derive(Debug, Default, FromBaseConfig)]
+pub struct ZipVoiceTTSConfig {
+    #[base_config(path = "src/tts/offline/mod.rs")]
+    base: TTSBaseConfig,
+
+    tokens: Option<CString>,
+    encoder: Option<CString>,
+    decoder: Option<CString>,
+    vocoder: Option<CString>,
+    data_dir: Option<CString>,
+    lexicon: Option<CString>,
+}
+
+impl ZipVoiceTTSConfig {
+    pub fn with_tokens(&mut self, tokens: &str) -> &mut Self {
+        let tokens = as_c_string!(tokens);
+        self.base.config.model.zipvoice.tokens = tokens.as_ptr();
+        self.tokens = Some(tokens);
+        self
+    }
+
+    pub fn with_encoder(&mut self, encoder: &str) -> &mut Self {
+        let encoder = as_c_string!(encoder);
+        self.base.config.model.zipvoice.encoder = encoder.as_ptr();
+        self.encoder = Some(encoder);
+        self
+    }
+
+    pub fn with_decoder(&mut self, decoder: &str) -> &mut Self {
+        let decoder = as_c_string!(decoder);
+        self.base.config.model.zipvoice.decoder = decoder.as_ptr();
+        self.decoder = Some(decoder);
+        self
+    }
+
+    pub fn with_vocoder(&mut self, vocoder: &str) -> &mut Self {
+        let vocoder = as_c_string!(vocoder);
+        self.base.config.model.zipvoice.vocoder = vocoder.as_ptr();
+        self.vocoder = Some(vocoder);
+        self
+    }
+
+    pub fn with_data_dir(&mut self, data_dir: &str) -> &mut Self {
+        let data_dir = as_c_string!(data_dir);
+        self.base.config.model.zipvoice.data_dir = data_dir.as_ptr();
+        self.data_dir = Some(data_dir);
+        self
+    }
+
+    pub fn with_lexicon(&mut self, lexicon: &str) -> &mut Self {
+        let lexicon = as_c_string!(lexicon);
+        self.base.config.model.zipvoice.lexicon = lexicon.as_ptr();
+        self.lexicon = Some(lexicon);
+        self
+    }
+
+    pub fn with_feat_scale(&mut self, feat_scale: f32) -> &mut Self {
+        self.base.config.model.zipvoice.feat_scale = feat_scale;
+        self
+    }
+
+    pub fn with_t_shift(&mut self, t_shift: f32) -> &mut Self {
+        self.base.config.model.zipvoice.t_shift = t_shift;
+        self
+    }
+
+    pub fn with_target_rms(&mut self, target_rms: f32) -> &mut Self {
+        self.base.config.model.zipvoice.target_rms = target_rms;
+        self
+    }
+
+    pub fn with_guidance_scale(&mut self, guidance_scale: f32) -> &mut Self {
+        self.base.config.model.zipvoice.guidance_scale = guidance_scale;
+        self
+    }
+}
+
+impl AsRef<sherpa_rs_sys::SherpaOnnxOfflineTtsConfig> for ZipVoiceTTSConfig {
+    fn as_ref(&self) -> &sherpa_rs_sys::SherpaOnnxOfflineTtsConfig {
+        &self.base.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::const_ptr_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_zipvoice_tts_config() {
+        let mut config = ZipVoiceTTSConfig::default();
+        config
+            .with_tokens("tokens.txt")
+            .with_encoder("encoder.onnx")
+            .with_decoder("decoder.onnx")
+            .with_vocoder("vocos_24khz.onnx")
+            .with_data_dir("espeak-ng-data")
+            .with_guidance_scale(1.0)
+            .with_t_shift(0.5);
+        let config: &sherpa_rs_sys::SherpaOnnxOfflineTtsConfig = config.as_ref();
+        assert_eq!(
+            const_ptr_to_string!(config.model.zipvoice.encoder),
+            "encoder.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model.zipvoice.decoder),
+            "decoder.onnx"
+        );
+        assert_eq!(
+            const_ptr_to_string!(config.model.zipvoice.vocoder),
+            "vocos_24khz.onnx"
+        );
+        assert_eq!(config.model.zipvoice.guidance_scale, 1.0);
+        assert_eq!(config.model.zipvoice.t_shift, 0.5);
+    }
+}
@@ -125,6 +125,12 @@ impl Vad {
             sherpa_rs_sys::SherpaOnnxVoiceActivityDetectorClear(self.0);
         }
     }
+
+    pub fn reset(&mut self) {
+        unsafe {
+            sherpa_rs_sys::SherpaOnnxVoiceActivityDetectorReset(self.0);
+        }
+    }
 }
 
 impl Drop for Vad {